@@ -42,6 +42,7 @@ impl Material for FireworksMaterial {
             },
             depth_test: DepthTest::Always,
             write_mask: WriteMask::COLOR,
+            ..Default::default()
         }
     }
     fn is_transparent(&self) -> bool {
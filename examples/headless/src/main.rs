@@ -41,27 +41,28 @@ fn main() {
         ColorMaterial::default(),
     );
 
-    // Create a color texture to render into
-    let mut texture = Texture2D::new_empty::<[u8; 4]>(
+    // Create a multisampled color texture and depth texture to render into (4 samples per pixel for antialiasing)
+    let mut multisample_texture =
+        Texture2DMultisample::new::<[u8; 4]>(&context, viewport.width, viewport.height, 4).unwrap();
+    let mut multisample_depth_texture = DepthTargetTexture2DMultisample::new(
         &context,
         viewport.width,
         viewport.height,
-        Interpolation::Nearest,
-        Interpolation::Nearest,
-        None,
-        Wrapping::ClampToEdge,
-        Wrapping::ClampToEdge,
+        4,
+        DepthFormat::Depth32F,
     )
     .unwrap();
 
-    // Also create a depth texture to support depth testing
-    let mut depth_texture = DepthTargetTexture2D::new(
+    // Create a color texture to resolve the multisampled color texture into
+    let mut texture = Texture2D::new_empty::<[u8; 4]>(
         &context,
         viewport.width,
         viewport.height,
+        Interpolation::Nearest,
+        Interpolation::Nearest,
+        None,
         Wrapping::ClampToEdge,
         Wrapping::ClampToEdge,
-        DepthFormat::Depth32F,
     )
     .unwrap();
 
@@ -73,9 +74,9 @@ fn main() {
         )));
 
         // Create a render target (a combination of a color and a depth texture) to write into
-        let pixels = RenderTarget::new(
-            texture.as_color_target(None),
-            depth_texture.as_depth_target(),
+        RenderTarget::new(
+            multisample_texture.as_color_target(),
+            multisample_depth_texture.as_depth_target(),
         )
         .unwrap()
         // Clear color and depth of the render target
@@ -83,11 +84,15 @@ fn main() {
         .unwrap()
         // Render the triangle with the per vertex colors defined at construction
         .render(&camera, &[&model], &[])
-        .unwrap()
-        // Read out the colors from the render target
-        .read_color()
         .unwrap();
 
+        // Resolve the multisampled colors into the color texture and read out the colors
+        multisample_texture
+            .as_color_target()
+            .resolve_to(&texture.as_color_target(None))
+            .unwrap();
+        let pixels = texture.as_color_target(None).read().unwrap();
+
         // Save the rendered image
         use three_d_asset::io::Serialize;
 
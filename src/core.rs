@@ -47,6 +47,29 @@ mod scissor_box;
 #[doc(inline)]
 pub use scissor_box::*;
 
+mod query;
+#[doc(inline)]
+pub use query::*;
+
+mod profiler;
+#[doc(inline)]
+pub use profiler::*;
+
+mod fence;
+#[doc(inline)]
+pub use fence::*;
+
+mod resource_stats;
+#[doc(inline)]
+pub use resource_stats::*;
+
+mod debug;
+#[doc(inline)]
+pub use debug::*;
+
+mod program_binary_cache;
+use program_binary_cache::*;
+
 pub use crate::ThreeDResult;
 use thiserror::Error;
 
@@ -79,10 +102,28 @@ pub enum CoreError {
     ShaderCompilation(String, String),
     #[error("failed to link shader program: {0}")]
     ShaderLink(String),
+    #[error(
+        "the shader chunk {0} included in {1} is neither registered nor in the built-in library"
+    )]
+    MissingShaderChunk(String, String),
     #[error("the uniform {0} is sent to the shader but not defined or never used")]
     UnusedUniform(String),
+    #[error("the uniform {0} is declared as {1} in the shader but the data sent is {2}")]
+    InvalidUniformType(String, String, String),
+    #[error("the uniform array {0} has length {1} but {2} elements are sent")]
+    InvalidUniformArrayLength(String, usize, usize),
     #[error("the attribute {0} is sent to the shader but not defined or never used")]
     UnusedAttribute(String),
+    #[error("the attribute {0} is not part of the vertex layout of the buffer")]
+    MissingVertexAttribute(String),
+    #[error("the vertex buffer does not have a vertex layout")]
+    MissingVertexLayout,
+    #[error("the program captures {0} output variables using transform feedback but {1} buffers are given")]
+    InvalidNumberOfTransformFeedbackBuffers(usize, usize),
+    #[error("the output variable {0} of type {1} cannot be captured using transform feedback")]
+    InvalidTransformFeedbackVarying(String, String),
+    #[error("invalid vertex layout: {0}")]
+    InvalidVertexLayout(String),
     #[error("failed creating a new render target: {0}")]
     RenderTargetCreation(String),
     #[error("cannot read {0} from a render target without {0}")]
@@ -93,8 +134,32 @@ pub enum CoreError {
     TextureCreation(String),
     #[error("invalid size of texture data (got {0} bytes but expected {1} bytes)")]
     InvalidTextureLength(usize, usize),
+    #[error(
+        "the number of samples must be in the range [1, {1}], actual number of samples is {0}"
+    )]
+    InvalidNumberOfSamples(u32, u32),
+    #[error("cannot read {0} from a multisample render target, resolve it to a non-multisample target first")]
+    MultisampleRead(String),
     #[error("all of the images used for cube map sides must have the same texture data type")]
     InvalidCubeMapTextureDataType,
+    #[error(
+        "all of the images used for texture array layers must have the same texture data type"
+    )]
+    InvalidTextureArrayTextureDataType,
+    #[error("all of the images used for texture array layers must have the same size, expected {0}x{1} but got {2}x{3}")]
+    InvalidTextureArrayTextureSize(u32, u32, u32, u32),
+    #[error("cannot create a texture array without any layers")]
+    EmptyTextureArray,
+    #[error("sRGB textures can only be created from RGB or RGBA byte data")]
+    InvalidSrgbTextureDataType,
+    #[error("integer textures only support nearest interpolation and no mip maps")]
+    InvalidIntegerTextureInterpolation,
+    #[error("a depth comparison function can only be used when sampling a depth texture")]
+    InvalidSamplerCompareFunction,
+    #[error("cannot convert an integer texture to a cpu texture, use a color target to read the integer data instead")]
+    IntegerTextureToCpuTexture,
+    #[error("the region {0:?} is not inside the texture with size {1}x{2}")]
+    InvalidTextureRegion(ScissorBox, u32, u32),
     #[error("the render call requires the {0} vertex buffer which is missing on the given mesh")]
     MissingMeshBuffer(String),
     #[error(
@@ -103,16 +168,36 @@ pub enum CoreError {
     MissingBitangent,
     #[error("{0} buffer length must be {1}, actual length is {2}")]
     InvalidBufferLength(String, usize, usize),
+    #[error("the data used to partially fill a buffer must be of the same type as the data in the buffer")]
+    InvalidBufferDataType,
+    #[error("the values from {0} to {1} are outside the buffer which contains {2} values")]
+    BufferRangeOutOfBounds(usize, usize, usize),
+    #[error("a buffer containing {0} values of {1} bytes each is too large to be allocated")]
+    BufferTooLarge(usize, usize),
     #[error("mesh must have both normals and uv coordinates to be able to compute tangents")]
     FailedComputingTangents,
     #[error("the number of vertices must be divisable by 3, actual count is {0}")]
     InvalidNumberOfVertices(usize),
     #[error("data for element at index {0} has length {1} but a length of {2} was expected")]
     InvalidUniformBufferElementLength(u32, usize, usize),
+    #[error("the uniform buffer has a size of {0} bytes but the data has a size of {1} bytes")]
+    InvalidUniformBufferSize(usize, usize),
     #[error("the index {0} is outside the expected range [0, {1}]")]
     IndexOutOfRange(usize, usize),
     #[error("cannot take as input a negative minimum distance")]
     NegativeDistance,
+    #[error("failed creating query: {0}")]
+    QueryCreation(String),
+    #[error("timer queries are not supported")]
+    TimerQueryNotSupported,
+    #[error("debug output is not supported")]
+    DebugOutputNotSupported,
+    #[error("failed creating fence: {0}")]
+    FenceCreation(String),
+    #[error("failed waiting for a fence to be signaled")]
+    FenceWait,
+    #[error("program binaries are not supported")]
+    ProgramBinaryNotSupported,
     #[error("a minimum must be smaller than a maximum")]
     MinimumLargerThanMaximum,
     #[error("the transformation matrix cannot be inverted and is therefore invalid")]
@@ -140,15 +225,36 @@ fn from_byte_slice<'a, T: DataType>(data: &'a [u8]) -> &'a [T] {
 }
 
 fn format_from_data_type<T: DataType>() -> u32 {
-    match T::size() {
-        1 => crate::context::RED,
-        2 => crate::context::RG,
-        3 => crate::context::RGB,
-        4 => crate::context::RGBA,
-        _ => unreachable!(),
+    if is_integer_data_type::<T>() {
+        match T::size() {
+            1 => crate::context::RED_INTEGER,
+            2 => crate::context::RG_INTEGER,
+            3 => crate::context::RGB_INTEGER,
+            4 => crate::context::RGBA_INTEGER,
+            _ => unreachable!(),
+        }
+    } else {
+        match T::size() {
+            1 => crate::context::RED,
+            2 => crate::context::RG,
+            3 => crate::context::RGB,
+            4 => crate::context::RGBA,
+            _ => unreachable!(),
+        }
     }
 }
 
+fn is_integer_data_type<T: DataType>() -> bool {
+    matches!(
+        T::data_type(),
+        crate::context::BYTE
+            | crate::context::SHORT
+            | crate::context::INT
+            | crate::context::UNSIGNED_SHORT
+            | crate::context::UNSIGNED_INT
+    )
+}
+
 fn flip_y<T: TextureDataType>(pixels: &mut [T], width: usize, height: usize) {
     for row in 0..height / 2 {
         for col in 0..width {
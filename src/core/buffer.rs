@@ -9,6 +9,10 @@ mod vertex_buffer;
 #[doc(inline)]
 pub use vertex_buffer::*;
 
+mod vertex_layout;
+#[doc(inline)]
+pub use vertex_layout::*;
+
 mod instance_buffer;
 #[doc(inline)]
 pub use instance_buffer::*;
@@ -17,6 +21,10 @@ mod uniform_buffer;
 #[doc(inline)]
 pub use uniform_buffer::*;
 
+mod std140;
+#[doc(inline)]
+pub use std140::*;
+
 use crate::core::*;
 use data_type::*;
 
@@ -43,16 +51,101 @@ impl BufferDataType for Quat {}
 
 impl<T: BufferDataType + ?Sized> BufferDataType for &T {}
 
+///
+/// A hint to the graphics driver about how often the data in a buffer is expected to change, which is used to optimize where the data is stored.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BufferUsage {
+    /// The data is set once and used many times.
+    Static,
+    /// The data is changed repeatedly and used many times.
+    Dynamic,
+    /// The data is changed repeatedly, typically every frame, and used only a few times between each change.
+    Stream,
+}
+
+fn usage_hint(usage: Option<BufferUsage>, is_filled: bool) -> u32 {
+    match usage {
+        Some(BufferUsage::Static) => crate::context::STATIC_DRAW,
+        Some(BufferUsage::Dynamic) => crate::context::DYNAMIC_DRAW,
+        Some(BufferUsage::Stream) => crate::context::STREAM_DRAW,
+        None => {
+            if is_filled {
+                crate::context::DYNAMIC_DRAW
+            } else {
+                crate::context::STATIC_DRAW
+            }
+        }
+    }
+}
+
+///
+/// Sends the data to the buffer bound to the given target.
+/// If the size of the data is the same as the size of the data already in the buffer and the buffer is not static,
+/// the buffer is orphaned before the data is sent, so that the graphics driver does not have to wait for draw calls using the old data to finish.
+///
+fn buffer_data(context: &Context, target: u32, data: &[u8], byte_count: usize, usage: u32) {
+    unsafe {
+        if !data.is_empty() && data.len() == byte_count && usage != crate::context::STATIC_DRAW {
+            context.buffer_data_size(target, data.len() as i32, usage);
+            context.buffer_sub_data_u8_slice(target, 0, data);
+        } else {
+            context.buffer_data_u8_slice(target, data, usage);
+        }
+    }
+}
+
+///
+/// Checks that the values from `offset` to `offset + length` are inside a buffer containing `count` values.
+///
+fn check_buffer_range(offset: u32, length: usize, count: usize) -> ThreeDResult<()> {
+    if offset as usize + length > count {
+        Err(CoreError::BufferRangeOutOfBounds(
+            offset as usize,
+            offset as usize + length,
+            count,
+        ))?;
+    }
+    Ok(())
+}
+
+///
+/// Returns the size in bytes of a single component of the given data type, for example 4 for `FLOAT`.
+///
+fn data_type_byte_size(data_type: u32) -> usize {
+    use crate::context::*;
+    match data_type {
+        BYTE | UNSIGNED_BYTE => 1,
+        SHORT | UNSIGNED_SHORT | HALF_FLOAT => 2,
+        _ => 4,
+    }
+}
+
+///
+/// Returns the number of bytes needed to store `count` values consisting of `data_size` components of the given data type.
+///
+fn allocation_byte_count(data_type: u32, data_size: u32, count: u32) -> ThreeDResult<usize> {
+    let value_size = data_size as usize * data_type_byte_size(data_type);
+    let byte_count = (count as usize)
+        .checked_mul(value_size)
+        .filter(|byte_count| *byte_count <= i32::MAX as usize)
+        .ok_or(CoreError::BufferTooLarge(count as usize, value_size))?;
+    Ok(byte_count)
+}
+
 struct Buffer {
     context: Context,
     id: crate::context::Buffer,
     attribute_count: u32,
     data_type: u32,
     data_size: u32,
+    byte_count: usize,
+    usage: Option<BufferUsage>,
+    resource: ResourceHandle,
 }
 
 impl Buffer {
-    pub fn new(context: &Context) -> ThreeDResult<Self> {
+    pub fn new(context: &Context, kind: ResourceKind) -> ThreeDResult<Self> {
         Ok(Self {
             context: context.clone(),
             id: unsafe {
@@ -63,11 +156,18 @@ impl Buffer {
             attribute_count: 0,
             data_type: 0,
             data_size: 0,
+            byte_count: 0,
+            usage: None,
+            resource: context.track_resource(kind, 0),
         })
     }
 
-    pub fn new_with_data<T: BufferDataType>(context: &Context, data: &[T]) -> ThreeDResult<Self> {
-        let mut buffer = Self::new(context)?;
+    pub fn new_with_data<T: BufferDataType>(
+        context: &Context,
+        kind: ResourceKind,
+        data: &[T],
+    ) -> ThreeDResult<Self> {
+        let mut buffer = Self::new(context, kind)?;
         if data.len() > 0 {
             buffer.fill(data)?;
         }
@@ -76,28 +176,99 @@ impl Buffer {
 
     pub fn fill<T: BufferDataType>(&mut self, data: &[T]) -> ThreeDResult<()> {
         self.bind();
+        buffer_data(
+            &self.context,
+            crate::context::ARRAY_BUFFER,
+            to_byte_slice(data),
+            self.byte_count,
+            usage_hint(self.usage, self.attribute_count > 0),
+        );
         unsafe {
-            self.context.buffer_data_u8_slice(
-                crate::context::ARRAY_BUFFER,
-                to_byte_slice(data),
-                if self.attribute_count > 0 {
-                    crate::context::DYNAMIC_DRAW
-                } else {
-                    crate::context::STATIC_DRAW
-                },
-            );
             self.context.bind_buffer(crate::context::ARRAY_BUFFER, None);
         }
         self.attribute_count = data.len() as u32;
         self.data_type = T::data_type();
         self.data_size = T::size();
+        self.byte_count = std::mem::size_of_val(data);
+        self.resource.set_byte_size(self.byte_count);
+        self.context.error_check()
+    }
+
+    pub fn fill_partially<T: BufferDataType>(
+        &mut self,
+        offset: u32,
+        data: &[T],
+    ) -> ThreeDResult<()> {
+        if T::data_type() != self.data_type || T::size() != self.data_size {
+            Err(CoreError::InvalidBufferDataType)?;
+        }
+        check_buffer_range(offset, data.len(), self.attribute_count as usize)?;
+        self.bind();
+        unsafe {
+            self.context.buffer_sub_data_u8_slice(
+                crate::context::ARRAY_BUFFER,
+                (offset as usize * std::mem::size_of::<T>()) as i32,
+                to_byte_slice(data),
+            );
+            self.context.bind_buffer(crate::context::ARRAY_BUFFER, None);
+        }
         self.context.error_check()
     }
 
+    pub fn allocate(&mut self, data_type: u32, data_size: u32, count: u32) -> ThreeDResult<()> {
+        let byte_count = allocation_byte_count(data_type, data_size, count)?;
+        self.bind();
+        unsafe {
+            self.context.buffer_data_size(
+                crate::context::ARRAY_BUFFER,
+                byte_count as i32,
+                usage_hint(self.usage, self.attribute_count > 0),
+            );
+            self.context.bind_buffer(crate::context::ARRAY_BUFFER, None);
+        }
+        self.attribute_count = count;
+        self.data_type = data_type;
+        self.data_size = data_size;
+        self.byte_count = byte_count;
+        self.resource.set_byte_size(byte_count);
+        self.context.error_check()
+    }
+
+    pub fn bind_base(&self, target: u32, index: u32) {
+        unsafe {
+            self.context.bind_buffer_base(target, index, Some(self.id));
+        }
+    }
+
+    pub fn set_usage(&mut self, usage: BufferUsage) {
+        self.usage = Some(usage);
+    }
+
     pub fn attribute_count(&self) -> u32 {
         self.attribute_count
     }
 
+    pub fn byte_count(&self) -> usize {
+        self.byte_count
+    }
+
+    pub fn set_label(&self, label: &str) {
+        self.resource.set_label(label);
+        self.context.label_object(
+            label,
+            crate::context::BUFFER,
+            crate::context::ARRAY_BUFFER_BINDING,
+            || self.bind(),
+        );
+        unsafe {
+            self.context.bind_buffer(crate::context::ARRAY_BUFFER, None);
+        }
+    }
+
+    pub fn label(&self) -> Option<String> {
+        self.resource.label()
+    }
+
     pub fn bind(&self) {
         unsafe {
             self.context
@@ -113,3 +284,32 @@ impl Drop for Buffer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allocation_byte_count_uses_the_size_of_the_data_type() {
+        use crate::context::*;
+        assert_eq!(allocation_byte_count(FLOAT, 3, 10).unwrap(), 120);
+        assert_eq!(allocation_byte_count(INT, 4, 10).unwrap(), 160);
+        assert_eq!(allocation_byte_count(UNSIGNED_SHORT, 2, 10).unwrap(), 40);
+        assert_eq!(allocation_byte_count(UNSIGNED_BYTE, 4, 10).unwrap(), 40);
+    }
+
+    #[test]
+    fn allocation_byte_count_overflow() {
+        use crate::context::*;
+        assert!(allocation_byte_count(FLOAT, 4, u32::MAX).is_err());
+        assert!(allocation_byte_count(FLOAT, 4, 1 << 29).is_err());
+        assert!(allocation_byte_count(FLOAT, 4, (1 << 27) - 1).is_ok());
+    }
+
+    #[test]
+    fn buffer_range() {
+        assert!(check_buffer_range(0, 10, 10).is_ok());
+        assert!(check_buffer_range(5, 5, 10).is_ok());
+        assert!(check_buffer_range(5, 6, 10).is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use super::{buffer_data, check_buffer_range, usage_hint};
 use crate::core::*;
 
 /// The basic data type used for each index in an element buffer.
@@ -33,6 +34,9 @@ pub struct ElementBuffer {
     id: crate::context::Buffer,
     count: usize,
     data_type: u32,
+    byte_count: usize,
+    usage: BufferUsage,
+    resource: ResourceHandle,
 }
 
 impl ElementBuffer {
@@ -50,6 +54,9 @@ impl ElementBuffer {
             id,
             count: 0,
             data_type: 0,
+            byte_count: 0,
+            usage: BufferUsage::Static,
+            resource: context.track_resource(ResourceKind::ElementBuffer, 0),
         })
     }
 
@@ -69,23 +76,65 @@ impl ElementBuffer {
 
     ///
     /// Fills the buffer with the given indices which must be divisable by 3.
+    /// If the buffer is filled repeatedly with data of the same size, the old data is discarded without waiting for the draw calls using it to finish, if the usage is set to [BufferUsage::Dynamic] or [BufferUsage::Stream].
     ///
     pub fn fill<T: ElementBufferDataType>(&mut self, data: &[T]) -> ThreeDResult<()> {
         self.bind();
+        buffer_data(
+            &self.context,
+            crate::context::ELEMENT_ARRAY_BUFFER,
+            to_byte_slice(data),
+            self.byte_count,
+            usage_hint(Some(self.usage), self.count > 0),
+        );
         unsafe {
-            self.context.buffer_data_u8_slice(
+            self.context
+                .bind_buffer(crate::context::ELEMENT_ARRAY_BUFFER, None);
+        }
+        self.count = data.len();
+        self.data_type = T::data_type();
+        self.byte_count = std::mem::size_of_val(data);
+        self.resource.set_byte_size(self.byte_count);
+        self.context.error_check()
+    }
+
+    ///
+    /// Fills the part of the buffer starting at the given offset with the given indices, leaving the rest of the buffer unchanged.
+    /// The offset is given as the number of indices from the start of the buffer.
+    ///
+    /// # Errors
+    /// Will return an error if the indices are not of the same type as the indices already in the buffer or if the indices do not fit inside the buffer.
+    ///
+    pub fn fill_partially<T: ElementBufferDataType>(
+        &mut self,
+        offset: u32,
+        data: &[T],
+    ) -> ThreeDResult<()> {
+        if T::data_type() != self.data_type {
+            Err(CoreError::InvalidBufferDataType)?;
+        }
+        check_buffer_range(offset, data.len(), self.count)?;
+        self.bind();
+        unsafe {
+            self.context.buffer_sub_data_u8_slice(
                 crate::context::ELEMENT_ARRAY_BUFFER,
+                (offset as usize * std::mem::size_of::<T>()) as i32,
                 to_byte_slice(data),
-                crate::context::STATIC_DRAW,
             );
             self.context
                 .bind_buffer(crate::context::ELEMENT_ARRAY_BUFFER, None);
         }
-        self.count = data.len();
-        self.data_type = T::data_type();
         self.context.error_check()
     }
 
+    ///
+    /// Specifies how often the indices in this buffer are expected to change, which is used as a hint to the graphics driver the next time the buffer is filled.
+    /// If not specified, [BufferUsage::Static] is used, since the indices rarely change.
+    ///
+    pub fn set_usage(&mut self, usage: BufferUsage) {
+        self.usage = usage;
+    }
+
     ///
     /// The number of values in the buffer.
     ///
@@ -100,6 +149,31 @@ impl ElementBuffer {
         self.count / 3
     }
 
+    ///
+    /// Sets a debug label for this buffer which is used to identify it in [Context::resource_stats]
+    /// and in GPU debuggers if debug output is supported, see [Context::supports_debug_output].
+    ///
+    pub fn set_label(&self, label: &str) {
+        self.resource.set_label(label);
+        self.context.label_object(
+            label,
+            crate::context::BUFFER,
+            crate::context::ELEMENT_ARRAY_BUFFER_BINDING,
+            || self.bind(),
+        );
+        unsafe {
+            self.context
+                .bind_buffer(crate::context::ELEMENT_ARRAY_BUFFER, None);
+        }
+    }
+
+    ///
+    /// Returns the debug label of this buffer, see [ElementBuffer::set_label].
+    ///
+    pub fn label(&self) -> Option<String> {
+        self.resource.label()
+    }
+
     pub(crate) fn bind(&self) {
         unsafe {
             self.context
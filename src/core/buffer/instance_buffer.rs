@@ -15,7 +15,7 @@ impl InstanceBuffer {
     ///
     pub fn new(context: &Context) -> ThreeDResult<Self> {
         Ok(Self {
-            buffer: Buffer::new(context)?,
+            buffer: Buffer::new(context, ResourceKind::InstanceBuffer)?,
         })
     }
 
@@ -25,18 +25,42 @@ impl InstanceBuffer {
     ///
     pub fn new_with_data<T: BufferDataType>(context: &Context, data: &[T]) -> ThreeDResult<Self> {
         Ok(Self {
-            buffer: Buffer::new_with_data(context, data)?,
+            buffer: Buffer::new_with_data(context, ResourceKind::InstanceBuffer, data)?,
         })
     }
 
     ///
     /// Fills the instance buffer with the given data. The data should be in the same format as specified in the shader.
     /// As an example, if specified as `vec3` in the shader it needs to be specified as an array of `Vector3<T>` where `T` is a primitive type that implements [BufferDataType], for example can be f16 or f32.
+    /// If the buffer is filled repeatedly with data of the same size, the old data is discarded without waiting for the draw calls using it to finish, unless the usage is [BufferUsage::Static].
     ///
     pub fn fill<T: BufferDataType>(&mut self, data: &[T]) -> ThreeDResult<()> {
         self.buffer.fill(data)
     }
 
+    ///
+    /// Fills the part of the instance buffer starting at the given offset with the given data, leaving the rest of the buffer unchanged.
+    /// The offset is given as the number of values of type `T` from the start of the buffer, so this is typically used to update the data for a few instances without sending all the data again.
+    ///
+    /// # Errors
+    /// Will return an error if the data is not of the same type as the data already in the buffer or if the data does not fit inside the buffer.
+    ///
+    pub fn fill_partially<T: BufferDataType>(
+        &mut self,
+        offset: u32,
+        data: &[T],
+    ) -> ThreeDResult<()> {
+        self.buffer.fill_partially(offset, data)
+    }
+
+    ///
+    /// Specifies how often the data in this buffer is expected to change, which is used as a hint to the graphics driver the next time the buffer is filled.
+    /// If not specified, [BufferUsage::Static] is used the first time the buffer is filled and [BufferUsage::Dynamic] afterwards.
+    ///
+    pub fn set_usage(&mut self, usage: BufferUsage) {
+        self.buffer.set_usage(usage);
+    }
+
     ///
     /// The number of values in the buffer.
     ///
@@ -51,6 +75,21 @@ impl InstanceBuffer {
         self.buffer.attribute_count()
     }
 
+    ///
+    /// Sets a debug label for this buffer which is used to identify it in [Context::resource_stats]
+    /// and in GPU debuggers if debug output is supported, see [Context::supports_debug_output].
+    ///
+    pub fn set_label(&self, label: &str) {
+        self.buffer.set_label(label);
+    }
+
+    ///
+    /// Returns the debug label of this buffer, see [InstanceBuffer::set_label].
+    ///
+    pub fn label(&self) -> Option<String> {
+        self.buffer.label()
+    }
+
     pub(crate) fn bind(&self) {
         self.buffer.bind();
     }
@@ -0,0 +1,360 @@
+use crate::core::*;
+
+///
+/// Implemented by types that can be written to a [UniformBuffer] laid out according to the std140 rules,
+/// which is the layout used by uniform blocks declared with `layout (std140)` in the shader.
+///
+/// The trait is implemented for scalars (`f32`, `i32`, `u32` and `bool`), vectors, matrices, [Color] (written as a `vec4`) and fixed size arrays of these.
+/// Use the [impl_std140](crate::impl_std140) macro to implement it for a struct, for example:
+///
+/// ```
+/// # use three_d::*;
+/// struct MaterialData {
+///     color: Vec4,
+///     metallic: f32,
+///     roughness: f32,
+///     offsets: [Vec2; 4],
+/// }
+/// impl_std140!(MaterialData {
+///     color,
+///     metallic,
+///     roughness,
+///     offsets
+/// });
+/// ```
+///
+/// which corresponds to the following block in the shader:
+///
+/// ```notrust
+/// layout (std140) uniform Material
+/// {
+///     vec4 color;
+///     float metallic;
+///     float roughness;
+///     vec2 offsets[4];
+/// } material;
+/// ```
+///
+pub trait Std140 {
+    ///
+    /// The base alignment in bytes of this type according to the std140 rules.
+    ///
+    fn alignment() -> usize;
+
+    ///
+    /// Writes this value to the given writer. The writer is already aligned to [Std140::alignment] when this method is called.
+    ///
+    fn write_std140(&self, writer: &mut Std140Writer);
+}
+
+///
+/// Writes data laid out according to the std140 rules, see [Std140].
+///
+#[derive(Clone, Debug, Default)]
+pub struct Std140Writer {
+    bytes: Vec<u8>,
+}
+
+impl Std140Writer {
+    ///
+    /// Creates a new empty writer.
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Aligns the given value and writes it.
+    ///
+    pub fn write<T: Std140>(&mut self, value: &T) {
+        self.align(T::alignment());
+        value.write_std140(self);
+    }
+
+    ///
+    /// Adds padding until the number of written bytes is a multiple of the given alignment.
+    ///
+    pub fn align(&mut self, alignment: usize) {
+        let padding = (alignment - self.bytes.len() % alignment) % alignment;
+        self.bytes.resize(self.bytes.len() + padding, 0);
+    }
+
+    ///
+    /// Writes the given 4 byte scalar without alignment.
+    ///
+    pub fn write_scalar(&mut self, bytes: [u8; 4]) {
+        self.bytes.extend_from_slice(&bytes);
+    }
+
+    ///
+    /// Returns the bytes written so far.
+    ///
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    ///
+    /// Consumes the writer and returns the written bytes.
+    ///
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+impl Std140 for f32 {
+    fn alignment() -> usize {
+        4
+    }
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        writer.write_scalar(self.to_ne_bytes());
+    }
+}
+
+impl Std140 for i32 {
+    fn alignment() -> usize {
+        4
+    }
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        writer.write_scalar(self.to_ne_bytes());
+    }
+}
+
+impl Std140 for u32 {
+    fn alignment() -> usize {
+        4
+    }
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        writer.write_scalar(self.to_ne_bytes());
+    }
+}
+
+impl Std140 for bool {
+    fn alignment() -> usize {
+        4
+    }
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        writer.write_scalar((*self as u32).to_ne_bytes());
+    }
+}
+
+impl<T: Std140> Std140 for Vector2<T> {
+    fn alignment() -> usize {
+        2 * T::alignment()
+    }
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        self.x.write_std140(writer);
+        self.y.write_std140(writer);
+    }
+}
+
+impl<T: Std140> Std140 for Vector3<T> {
+    fn alignment() -> usize {
+        4 * T::alignment()
+    }
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        self.x.write_std140(writer);
+        self.y.write_std140(writer);
+        self.z.write_std140(writer);
+    }
+}
+
+impl<T: Std140> Std140 for Vector4<T> {
+    fn alignment() -> usize {
+        4 * T::alignment()
+    }
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        self.x.write_std140(writer);
+        self.y.write_std140(writer);
+        self.z.write_std140(writer);
+        self.w.write_std140(writer);
+    }
+}
+
+impl Std140 for Mat2 {
+    fn alignment() -> usize {
+        16
+    }
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        [self.x, self.y].write_std140(writer);
+    }
+}
+
+impl Std140 for Mat3 {
+    fn alignment() -> usize {
+        16
+    }
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        [self.x, self.y, self.z].write_std140(writer);
+    }
+}
+
+impl Std140 for Mat4 {
+    fn alignment() -> usize {
+        16
+    }
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        [self.x, self.y, self.z, self.w].write_std140(writer);
+    }
+}
+
+impl Std140 for Color {
+    fn alignment() -> usize {
+        16
+    }
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        self.to_vec4().write_std140(writer);
+    }
+}
+
+impl<T: Std140, const N: usize> Std140 for [T; N] {
+    fn alignment() -> usize {
+        T::alignment().max(16)
+    }
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        for element in self.iter() {
+            writer.align(Self::alignment());
+            element.write_std140(writer);
+        }
+        writer.align(Self::alignment());
+    }
+}
+
+///
+/// Implements the [Std140] trait for a struct by writing the given fields in the given order,
+/// which should be the same order as the members of the uniform block in the shader.
+/// All fields must implement [Std140]. See [Std140] for an example.
+///
+/// This is a declarative macro rather than a `#[derive(Std140)]` since a derive requires a separate proc-macro crate.
+/// The fields are listed explicitly which also makes it possible to leave out fields that are not part of the uniform block.
+///
+#[macro_export]
+macro_rules! impl_std140 {
+    ($type:ty { $($field:ident),* $(,)? }) => {
+        impl $crate::core::Std140 for $type {
+            fn alignment() -> usize {
+                16
+            }
+            fn write_std140(&self, writer: &mut $crate::core::Std140Writer) {
+                $(writer.write(&self.$field);)*
+                writer.align(16);
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bytes<T: Std140>(value: &T) -> Vec<u8> {
+        let mut writer = Std140Writer::new();
+        writer.write(value);
+        writer.into_bytes()
+    }
+
+    fn floats(bytes: &[u8]) -> Vec<f32> {
+        bytes
+            .chunks(4)
+            .map(|b| f32::from_ne_bytes([b[0], b[1], b[2], b[3]]))
+            .collect()
+    }
+
+    struct Block {
+        a: Vec3,
+        b: f32,
+        c: Vec2,
+        d: [f32; 2],
+        e: Vec3,
+    }
+    impl_std140!(Block { a, b, c, d, e });
+
+    struct Outer {
+        a: f32,
+        inner: Inner,
+        b: f32,
+    }
+    struct Inner {
+        x: f32,
+    }
+    impl_std140!(Inner { x });
+    impl_std140!(Outer { a, inner, b });
+
+    #[test]
+    fn vec3_is_aligned_to_16_bytes() {
+        let mut writer = Std140Writer::new();
+        writer.write(&1.0f32);
+        writer.write(&vec3(2.0, 3.0, 4.0));
+        writer.write(&5.0f32);
+        assert_eq!(
+            floats(writer.bytes()),
+            vec![1.0, 0.0, 0.0, 0.0, 2.0, 3.0, 4.0, 5.0]
+        );
+    }
+
+    #[test]
+    fn array_elements_have_a_stride_of_16_bytes() {
+        let data = bytes(&[1.0f32, 2.0, 3.0]);
+        assert_eq!(data.len(), 48);
+        assert_eq!(
+            floats(&data),
+            vec![1.0, 0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 3.0, 0.0, 0.0, 0.0]
+        );
+        assert_eq!(bytes(&[vec2(1.0, 2.0); 2]).len(), 32);
+    }
+
+    #[test]
+    fn matrix_columns_are_aligned_like_vec4_arrays() {
+        assert_eq!(bytes(&Mat3::from_value(1.0)).len(), 48);
+        assert_eq!(bytes(&Mat4::from_value(1.0)).len(), 64);
+    }
+
+    #[test]
+    fn struct_members_follow_the_std140_rules() {
+        let data = bytes(&Block {
+            a: vec3(1.0, 2.0, 3.0),
+            b: 4.0,
+            c: vec2(5.0, 6.0),
+            d: [7.0, 8.0],
+            e: vec3(9.0, 10.0, 11.0),
+        });
+        assert_eq!(
+            floats(&data),
+            vec![
+                1.0, 2.0, 3.0, 4.0, // a at 0, b at 12
+                5.0, 6.0, 0.0, 0.0, // c at 16
+                7.0, 0.0, 0.0, 0.0, // d[0] at 32
+                8.0, 0.0, 0.0, 0.0, // d[1] at 48
+                9.0, 10.0, 11.0, 0.0, // e at 64
+            ]
+        );
+    }
+
+    #[test]
+    fn nested_structs_are_aligned_to_16_bytes() {
+        let data = bytes(&Outer {
+            a: 1.0,
+            inner: Inner { x: 2.0 },
+            b: 3.0,
+        });
+        assert_eq!(
+            floats(&data),
+            vec![
+                1.0, 0.0, 0.0, 0.0, // a at 0
+                2.0, 0.0, 0.0, 0.0, // inner at 16, padded to 16 bytes
+                3.0, 0.0, 0.0, 0.0, // b at 32
+            ]
+        );
+    }
+
+    #[test]
+    fn integers_and_booleans_are_written_as_4_byte_scalars() {
+        let mut writer = Std140Writer::new();
+        writer.write(&-1i32);
+        writer.write(&true);
+        writer.write(&7u32);
+        let data = writer.into_bytes();
+        assert_eq!(&data[0..4], &(-1i32).to_ne_bytes());
+        assert_eq!(&data[4..8], &1u32.to_ne_bytes());
+        assert_eq!(&data[8..12], &7u32.to_ne_bytes());
+    }
+}
@@ -3,12 +3,14 @@ use crate::core::*;
 ///
 /// A buffer for transferring a set of uniform variables to the shader program
 /// (see also [use_uniform_block](crate::core::Program::use_uniform_block)).
+/// Use [UniformBuffer::new_with_data] to create a buffer from a type that is laid out according to the std140 rules, see [Std140].
 ///
 pub struct UniformBuffer {
     context: Context,
     id: crate::context::Buffer,
     offsets: Vec<usize>,
     data: Vec<f32>,
+    resource: ResourceHandle,
 }
 
 impl UniformBuffer {
@@ -18,30 +20,82 @@ impl UniformBuffer {
     /// The first with 3 elements (a [Vec3]), the second with 1 element (a `f32`), the third with four elements (a [Vec4]) and the last with 16 elements (a [Mat4]).
     /// The variables are initialized to 0.
     ///
+    /// **Note:** The variables are packed without any padding, so they do not necessarily match the std140 layout of a uniform block in the shader.
+    /// Use [UniformBuffer::new_with_data] instead to avoid calculating the padding by hand.
+    ///
     pub fn new(context: &Context, sizes: &[u32]) -> ThreeDResult<UniformBuffer> {
-        let id = unsafe {
-            context
-                .create_buffer()
-                .map_err(|e| CoreError::BufferCreation(e))?
-        };
-
         let mut offsets = Vec::new();
         let mut length = 0;
         for size in sizes {
             offsets.push(length);
             length += *size as usize;
         }
+        let buffer = Self::new_with_offsets(context, offsets, vec![0.0; length])?;
+        buffer.send(to_byte_slice(&buffer.data));
+        context.error_check()?;
+        Ok(buffer)
+    }
+
+    ///
+    /// Creates a new uniform buffer containing the given data laid out according to the std140 rules, see [Std140].
+    /// The buffer can then be used for a uniform block declared with `layout (std140)` in the shader
+    /// and the data can be updated using [UniformBuffer::fill].
+    ///
+    pub fn new_with_data<T: Std140>(context: &Context, data: &T) -> ThreeDResult<UniformBuffer> {
+        let bytes = std140_bytes(data);
+        let mut buffer = Self::new_with_offsets(context, vec![0], vec![0.0; bytes.len() / 4])?;
+        buffer.set_bytes(&bytes);
+        context.error_check()?;
+        Ok(buffer)
+    }
+
+    fn new_with_offsets(
+        context: &Context,
+        offsets: Vec<usize>,
+        data: Vec<f32>,
+    ) -> ThreeDResult<UniformBuffer> {
+        let id = unsafe { context.create_buffer().map_err(CoreError::BufferCreation)? };
         let buffer = UniformBuffer {
             context: context.clone(),
             id,
             offsets,
-            data: vec![0.0; length as usize],
+            resource: context.track_resource(
+                ResourceKind::UniformBuffer,
+                std::mem::size_of_val(data.as_slice()),
+            ),
+            data,
         };
-        buffer.send();
-        context.error_check()?;
         Ok(buffer)
     }
 
+    ///
+    /// Sets a debug label for this buffer which is used to identify it in [Context::resource_stats]
+    /// and in GPU debuggers if debug output is supported, see [Context::supports_debug_output].
+    ///
+    pub fn set_label(&self, label: &str) {
+        self.resource.set_label(label);
+        self.context.label_object(
+            label,
+            crate::context::BUFFER,
+            crate::context::UNIFORM_BUFFER_BINDING,
+            || unsafe {
+                self.context
+                    .bind_buffer(crate::context::UNIFORM_BUFFER, Some(self.id));
+            },
+        );
+        unsafe {
+            self.context
+                .bind_buffer(crate::context::UNIFORM_BUFFER, None);
+        }
+    }
+
+    ///
+    /// Returns the debug label of this buffer, see [UniformBuffer::set_label].
+    ///
+    pub fn label(&self) -> Option<String> {
+        self.resource.label()
+    }
+
     pub(crate) fn bind(&self, id: u32) {
         unsafe {
             self.context
@@ -67,13 +121,32 @@ impl UniformBuffer {
         }
         self.data
             .splice(offset..offset + length, data.iter().cloned());
-        self.send();
+        self.send(to_byte_slice(&self.data));
         //TODO: Send to GPU (contextBufferSubData)
         self.context.error_check()
     }
 
+    ///
+    /// Replaces the entire content of this buffer with the given data laid out according to the std140 rules, see [Std140].
+    ///
+    /// # Errors
+    /// Will return an error if the size of the data, including the padding required by the std140 rules, does not match the size of this buffer.
+    ///
+    pub fn fill<T: Std140>(&mut self, data: &T) -> ThreeDResult<()> {
+        let bytes = std140_bytes(data);
+        if bytes.len() != 4 * self.data.len() {
+            Err(CoreError::InvalidUniformBufferSize(
+                4 * self.data.len(),
+                bytes.len(),
+            ))?;
+        }
+        self.set_bytes(&bytes);
+        self.context.error_check()
+    }
+
     ///
     /// Returns the values of the variable at the given index.
+    /// If the buffer is created using [UniformBuffer::new_with_data], there is only one variable which contains the entire buffer.
     ///
     /// # Errors
     /// Will return an error if the index is not in the range `[0-max]` where `max` is the length of the `sizes` argument given at construction.
@@ -96,13 +169,22 @@ impl UniformBuffer {
         Ok((offset, length))
     }
 
-    fn send(&self) {
+    fn set_bytes(&mut self, bytes: &[u8]) {
+        // Keep a copy of the raw bytes for [UniformBuffer::get] and upload the bytes as they are.
+        unsafe {
+            std::slice::from_raw_parts_mut(self.data.as_mut_ptr() as *mut u8, 4 * self.data.len())
+                .copy_from_slice(bytes);
+        }
+        self.send(bytes);
+    }
+
+    fn send(&self, bytes: &[u8]) {
         unsafe {
             self.context
                 .bind_buffer(crate::context::UNIFORM_BUFFER, Some(self.id));
             self.context.buffer_data_u8_slice(
                 crate::context::UNIFORM_BUFFER,
-                to_byte_slice(&self.data),
+                bytes,
                 crate::context::STATIC_DRAW,
             );
             self.context
@@ -118,3 +200,10 @@ impl Drop for UniformBuffer {
         }
     }
 }
+
+fn std140_bytes<T: Std140>(data: &T) -> Vec<u8> {
+    let mut writer = Std140Writer::new();
+    writer.write(data);
+    writer.align(16);
+    writer.into_bytes()
+}
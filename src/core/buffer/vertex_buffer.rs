@@ -5,8 +5,12 @@ use crate::core::*;
 /// A buffer containing per vertex data, for example positions, normals, uv coordinates or colors.
 /// To send this data to a shader, use the [Program::use_vertex_attribute] method.
 ///
+/// The buffer can also contain the data for several attributes interleaved, in which case the layout of each vertex is described by a [VertexLayout],
+/// see [VertexBuffer::new_with_interleaved_data].
+///
 pub struct VertexBuffer {
     buffer: Buffer,
+    layout: Option<VertexLayout>,
 }
 
 impl VertexBuffer {
@@ -15,7 +19,8 @@ impl VertexBuffer {
     ///
     pub fn new(context: &Context) -> ThreeDResult<Self> {
         Ok(Self {
-            buffer: Buffer::new(context)?,
+            buffer: Buffer::new(context, ResourceKind::VertexBuffer)?,
+            layout: None,
         })
     }
 
@@ -25,36 +30,160 @@ impl VertexBuffer {
     ///
     pub fn new_with_data<T: BufferDataType>(context: &Context, data: &[T]) -> ThreeDResult<Self> {
         Ok(Self {
-            buffer: Buffer::new_with_data(context, data)?,
+            buffer: Buffer::new_with_data(context, ResourceKind::VertexBuffer, data)?,
+            layout: None,
         })
     }
 
+    ///
+    /// Creates a new vertex buffer containing the data for several attributes interleaved as described by the given layout.
+    /// The data is given as raw values, for example `f32` if all attributes consist of floats or `u8` if the attributes are of different types,
+    /// and the data for each vertex must be placed according to the offsets and stride of the layout.
+    /// To send the data to a shader, use the [Program::use_vertex_buffer] or [Program::use_vertex_attribute] methods.
+    ///
+    /// # Errors
+    /// Will return an error if an attribute in the layout has an invalid number of components or does not fit within the stride
+    /// or if the size of the data is not a multiple of the stride.
+    ///
+    pub fn new_with_interleaved_data<T: BufferDataType>(
+        context: &Context,
+        layout: VertexLayout,
+        data: &[T],
+    ) -> ThreeDResult<Self> {
+        layout.check()?;
+        let mut buffer = Self {
+            buffer: Buffer::new(context, ResourceKind::VertexBuffer)?,
+            layout: Some(layout),
+        };
+        buffer.fill_interleaved(data)?;
+        Ok(buffer)
+    }
+
     ///
     /// Fills the vertex buffer with the given data. The data should be in the same format as specified in the shader.
     /// As an example, if specified as `vec3` in the shader it needs to be specified as an array of `Vector3<T>` where `T` is a primitive type that implements [BufferDataType], for example can be f16 or f32.
+    /// If the buffer is filled repeatedly with data of the same size, the old data is discarded without waiting for the draw calls using it to finish, unless the usage is [BufferUsage::Static].
+    /// If the buffer contained interleaved data, the vertex layout is removed.
     ///
     pub fn fill<T: BufferDataType>(&mut self, data: &[T]) -> ThreeDResult<()> {
+        self.layout = None;
         self.buffer.fill(data)
     }
 
+    ///
+    /// Fills the vertex buffer with the given interleaved data, placed according to the layout given at construction (see [VertexBuffer::new_with_interleaved_data]).
+    ///
+    /// # Errors
+    /// Will return an error if this buffer does not have a vertex layout or if the size of the data is not a multiple of the stride.
+    ///
+    pub fn fill_interleaved<T: BufferDataType>(&mut self, data: &[T]) -> ThreeDResult<()> {
+        let stride = self
+            .layout
+            .as_ref()
+            .ok_or(CoreError::MissingVertexLayout)?
+            .stride as usize;
+        let byte_count = std::mem::size_of_val(data);
+        let remainder = byte_count % stride;
+        if remainder != 0 {
+            Err(CoreError::InvalidVertexLayout(format!(
+                "the data has a size of {} bytes which is not a multiple of the stride of {} bytes, {} bytes are left over",
+                byte_count, stride, remainder
+            )))?;
+        }
+        self.buffer.fill(data)
+    }
+
+    ///
+    /// Returns the layout of the vertices if this buffer contains interleaved data, otherwise `None`.
+    ///
+    pub fn layout(&self) -> Option<&VertexLayout> {
+        self.layout.as_ref()
+    }
+
+    ///
+    /// Fills the part of the vertex buffer starting at the given offset with the given data, leaving the rest of the buffer unchanged.
+    /// The offset is given as the number of values of type `T` from the start of the buffer, so this is typically used to update the data for a few vertices without sending all the data again.
+    ///
+    /// # Errors
+    /// Will return an error if the data is not of the same type as the data already in the buffer or if the data does not fit inside the buffer.
+    ///
+    pub fn fill_partially<T: BufferDataType>(
+        &mut self,
+        offset: u32,
+        data: &[T],
+    ) -> ThreeDResult<()> {
+        self.buffer.fill_partially(offset, data)
+    }
+
+    ///
+    /// Specifies how often the data in this buffer is expected to change, which is used as a hint to the graphics driver the next time the buffer is filled.
+    /// If not specified, [BufferUsage::Static] is used the first time the buffer is filled and [BufferUsage::Dynamic] afterwards.
+    ///
+    pub fn set_usage(&mut self, usage: BufferUsage) {
+        self.buffer.set_usage(usage);
+    }
+
     ///
     /// The number of values in the buffer.
     ///
     pub fn count(&self) -> u32 {
-        self.buffer.attribute_count() * self.buffer.data_size
+        if let Some(layout) = &self.layout {
+            self.vertex_count()
+                * layout
+                    .attributes
+                    .iter()
+                    .map(|a| a.component_count)
+                    .sum::<u32>()
+        } else {
+            self.buffer.attribute_count() * self.buffer.data_size
+        }
     }
 
     ///
     /// The number of vertex attributes in the buffer.
     ///
     pub fn vertex_count(&self) -> u32 {
-        self.buffer.attribute_count()
+        if let Some(layout) = &self.layout {
+            (self.buffer.byte_count() / layout.stride as usize) as u32
+        } else {
+            self.buffer.attribute_count()
+        }
+    }
+
+    ///
+    /// Sets a debug label for this buffer which is used to identify it in [Context::resource_stats]
+    /// and in GPU debuggers if debug output is supported, see [Context::supports_debug_output].
+    ///
+    pub fn set_label(&self, label: &str) {
+        self.buffer.set_label(label);
+    }
+
+    ///
+    /// Returns the debug label of this buffer, see [VertexBuffer::set_label].
+    ///
+    pub fn label(&self) -> Option<String> {
+        self.buffer.label()
     }
 
     pub(crate) fn bind(&self) {
         self.buffer.bind();
     }
 
+    pub(crate) fn allocate(
+        &mut self,
+        data_type: u32,
+        data_size: u32,
+        count: u32,
+    ) -> ThreeDResult<()> {
+        self.layout = None;
+        self.buffer.allocate(data_type, data_size, count)
+    }
+
+    pub(crate) fn bind_as_transform_feedback_output(&self, index: u32) {
+        self.buffer
+            .bind_base(crate::context::TRANSFORM_FEEDBACK_BUFFER, index);
+    }
+
     pub(crate) fn data_type(&self) -> u32 {
         self.buffer.data_type
     }
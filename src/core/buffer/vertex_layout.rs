@@ -0,0 +1,242 @@
+use crate::core::*;
+
+///
+/// The data type of each component of a [VertexAttribute].
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ComponentType {
+    /// Unsigned 8 bit integer.
+    U8,
+    /// Unsigned 16 bit integer.
+    U16,
+    /// Unsigned 32 bit integer.
+    U32,
+    /// Signed 8 bit integer.
+    I8,
+    /// Signed 16 bit integer.
+    I16,
+    /// Signed 32 bit integer.
+    I32,
+    /// 16 bit float.
+    F16,
+    /// 32 bit float.
+    F32,
+}
+
+impl ComponentType {
+    ///
+    /// The size of one component of this type in bytes.
+    ///
+    pub fn byte_size(&self) -> u32 {
+        match self {
+            ComponentType::U8 | ComponentType::I8 => 1,
+            ComponentType::U16 | ComponentType::I16 | ComponentType::F16 => 2,
+            ComponentType::U32 | ComponentType::I32 | ComponentType::F32 => 4,
+        }
+    }
+
+    ///
+    /// Returns whether or not this is an integer type.
+    ///
+    pub fn is_integer(&self) -> bool {
+        !matches!(self, ComponentType::F16 | ComponentType::F32)
+    }
+
+    pub(crate) fn data_type(&self) -> u32 {
+        match self {
+            ComponentType::U8 => crate::context::UNSIGNED_BYTE,
+            ComponentType::U16 => crate::context::UNSIGNED_SHORT,
+            ComponentType::U32 => crate::context::UNSIGNED_INT,
+            ComponentType::I8 => crate::context::BYTE,
+            ComponentType::I16 => crate::context::SHORT,
+            ComponentType::I32 => crate::context::INT,
+            ComponentType::F16 => crate::context::HALF_FLOAT,
+            ComponentType::F32 => crate::context::FLOAT,
+        }
+    }
+}
+
+///
+/// Describes where to find the data for one vertex attribute within each vertex of an interleaved [VertexBuffer], see [VertexLayout].
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VertexAttribute {
+    /// The name of the attribute as it is declared in the vertex shader.
+    pub name: String,
+    /// The offset in bytes from the start of a vertex to the first component of this attribute.
+    pub offset: u32,
+    /// The data type of each component.
+    pub component_type: ComponentType,
+    /// The number of components, for example 3 for a `vec3`. Must be between 1 and 4.
+    pub component_count: u32,
+    ///
+    /// Whether or not integer data is normalized when it is read in the shader, ie. mapped to the range `0.0..=1.0` for unsigned types and `-1.0..=1.0` for signed types.
+    /// A normalized attribute is declared as a float type in the shader, for example `vec4` for a color stored as four `u8`,
+    /// whereas an integer attribute which is not normalized must be declared as an integer type, for example `uint` or `ivec2`.
+    /// Has no effect for float types.
+    ///
+    pub normalized: bool,
+}
+
+impl VertexAttribute {
+    ///
+    /// The size of this attribute in bytes.
+    ///
+    pub fn byte_size(&self) -> u32 {
+        self.component_count * self.component_type.byte_size()
+    }
+
+    ///
+    /// Returns whether or not the attribute is read as integers in the shader, ie. whether the component type is an integer type that is not normalized.
+    ///
+    pub fn is_integer(&self) -> bool {
+        self.component_type.is_integer() && !self.normalized
+    }
+}
+
+///
+/// Describes the layout of the vertices in an interleaved [VertexBuffer], ie. a buffer where the data for several attributes are stored together for each vertex.
+/// Use [VertexLayout::with_attribute] to add the attributes one after the other or specify the offsets and stride manually.
+///
+/// ```
+/// # use three_d::*;
+/// // Each vertex consists of a position (3 x f32), a normal (3 x f32) and a color (4 x u8).
+/// let layout = VertexLayout::new()
+///     .with_attribute("position", ComponentType::F32, 3, false)
+///     .with_attribute("normal", ComponentType::F32, 3, false)
+///     .with_attribute("color", ComponentType::U8, 4, true);
+/// assert_eq!(layout.stride, 28);
+/// ```
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VertexLayout {
+    /// The number of bytes from the start of one vertex to the start of the next.
+    pub stride: u32,
+    /// The attributes in each vertex.
+    pub attributes: Vec<VertexAttribute>,
+}
+
+impl VertexLayout {
+    ///
+    /// Creates a new layout without any attributes.
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Adds an attribute after the already added attributes and updates the stride accordingly.
+    /// The offset of the attribute is aligned to the size of the component type.
+    ///
+    pub fn with_attribute(
+        mut self,
+        name: &str,
+        component_type: ComponentType,
+        component_count: u32,
+        normalized: bool,
+    ) -> Self {
+        let attribute = VertexAttribute {
+            name: name.to_string(),
+            offset: align(self.stride, component_type.byte_size()),
+            component_type,
+            component_count,
+            normalized,
+        };
+        let alignment = self
+            .attributes
+            .iter()
+            .chain(std::iter::once(&attribute))
+            .map(|a| a.component_type.byte_size())
+            .max()
+            .unwrap();
+        self.stride = align(attribute.offset + attribute.byte_size(), alignment);
+        self.attributes.push(attribute);
+        self
+    }
+
+    ///
+    /// Returns the attribute with the given name if it is part of this layout.
+    ///
+    pub fn attribute(&self, name: &str) -> Option<&VertexAttribute> {
+        self.attributes.iter().find(|a| a.name == name)
+    }
+
+    pub(crate) fn check(&self) -> ThreeDResult<()> {
+        if self.stride == 0 {
+            Err(CoreError::InvalidVertexLayout(
+                "the stride must be larger than zero".to_string(),
+            ))?;
+        }
+        for attribute in self.attributes.iter() {
+            if attribute.component_count == 0 || attribute.component_count > 4 {
+                Err(CoreError::InvalidVertexLayout(format!(
+                    "the attribute {} has {} components but must have between 1 and 4",
+                    attribute.name, attribute.component_count
+                )))?;
+            }
+            if attribute.offset + attribute.byte_size() > self.stride {
+                Err(CoreError::InvalidVertexLayout(format!(
+                    "the attribute {} does not fit within the stride of {} bytes",
+                    attribute.name, self.stride
+                )))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+///
+/// Rounds the given value up to the nearest multiple of the alignment.
+///
+fn align(value: u32, alignment: u32) -> u32 {
+    value + (alignment - value % alignment) % alignment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offsets_are_aligned_to_the_component_size() {
+        let layout = VertexLayout::new()
+            .with_attribute("id", ComponentType::U8, 1, false)
+            .with_attribute("uv", ComponentType::U16, 2, true)
+            .with_attribute("position", ComponentType::F32, 3, false);
+        let offsets = layout
+            .attributes
+            .iter()
+            .map(|a| (a.name.as_str(), a.offset))
+            .collect::<Vec<_>>();
+        assert_eq!(offsets, vec![("id", 0), ("uv", 2), ("position", 8)]);
+        assert_eq!(layout.stride, 20);
+        assert!(layout.check().is_ok());
+    }
+
+    #[test]
+    fn stride_is_aligned_to_the_largest_component_size() {
+        let layout = VertexLayout::new()
+            .with_attribute("position", ComponentType::F32, 3, false)
+            .with_attribute("color", ComponentType::U8, 3, true);
+        assert_eq!(layout.attribute("color").unwrap().offset, 12);
+        assert_eq!(layout.stride, 16);
+
+        let layout = VertexLayout::new()
+            .with_attribute("a", ComponentType::U8, 1, false)
+            .with_attribute("b", ComponentType::I16, 1, false);
+        assert_eq!(layout.stride, 4);
+
+        let layout = VertexLayout::new().with_attribute("c", ComponentType::U8, 3, true);
+        assert_eq!(layout.stride, 3);
+    }
+
+    #[test]
+    fn invalid_layouts() {
+        assert!(VertexLayout::new().check().is_err());
+        let mut layout =
+            VertexLayout::new().with_attribute("position", ComponentType::F32, 3, false);
+        layout.stride = 8;
+        assert!(layout.check().is_err());
+        let layout = VertexLayout::new().with_attribute("position", ComponentType::F32, 5, false);
+        assert!(layout.check().is_err());
+    }
+}
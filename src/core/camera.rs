@@ -391,7 +391,15 @@ impl Camera {
             projection_type: ProjectionType::Orthographic { height: 1.0 },
             z_near: 0.0,
             z_far: 0.0,
-            uniform_buffer: UniformBuffer::new(context, &[16, 16, 16, 3, 1])?,
+            uniform_buffer: UniformBuffer::new_with_data(
+                context,
+                &CameraUniforms {
+                    view_projection: Mat4::identity(),
+                    view: Mat4::identity(),
+                    projection: Mat4::identity(),
+                    position: vec3(0.0, 0.0, 5.0),
+                },
+            )?,
             frustrum: [vec4(0.0, 0.0, 0.0, 0.0); 6],
             position: vec3(0.0, 0.0, 5.0),
             target: vec3(0.0, 0.0, 0.0),
@@ -409,20 +417,12 @@ impl Camera {
     }
 
     fn update_uniform_buffer(&mut self) -> ThreeDResult<()> {
-        let as_array = |m: Mat4| {
-            [
-                m.x.x, m.x.y, m.x.z, m.x.w, m.y.x, m.y.y, m.y.z, m.y.w, m.z.x, m.z.y, m.z.z, m.z.w,
-                m.w.x, m.w.y, m.w.z, m.w.w,
-            ]
-        };
-
-        self.uniform_buffer
-            .update(0, &as_array(self.projection * self.view))?;
-        self.uniform_buffer.update(1, &as_array(self.view))?;
-        self.uniform_buffer.update(2, &as_array(self.projection))?;
-        self.uniform_buffer
-            .update(3, &[self.position.x, self.position.y, self.position.z])?;
-        Ok(())
+        self.uniform_buffer.fill(&CameraUniforms {
+            view_projection: self.projection * self.view,
+            view: self.view,
+            projection: self.projection,
+            position: self.position,
+        })
     }
 
     fn update_frustrum(&mut self) {
@@ -567,3 +567,17 @@ impl Camera {
         Ok(())
     }
 }
+
+struct CameraUniforms {
+    view_projection: Mat4,
+    view: Mat4,
+    projection: Mat4,
+    position: Vec3,
+}
+
+crate::impl_std140!(CameraUniforms {
+    view_projection,
+    view,
+    projection,
+    position
+});
@@ -1,5 +1,5 @@
 use super::*;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::rc::Rc;
 use std::sync::Arc;
@@ -18,7 +18,18 @@ pub struct Context {
     pub(super) vao: crate::context::VertexArray,
     programs: Rc<RefCell<HashMap<String, Program>>>,
     effects: Rc<RefCell<HashMap<String, ImageEffect>>>,
+    shader_chunks: Rc<RefCell<HashMap<String, String>>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    shader_files: Rc<RefCell<HashMap<String, (std::path::PathBuf, std::time::SystemTime)>>>,
     camera2d: Rc<RefCell<Option<Camera>>>,
+    pub(super) state_cache: Rc<RefCell<StateCache>>,
+    resources: Rc<RefCell<ResourceRegistry>>,
+    pub(super) program_binary_cache: Rc<RefCell<ProgramBinaryCache>>,
+    depth_test_without_depth_write: Rc<Cell<bool>>,
+    srgb_color_textures: Rc<Cell<bool>>,
+    pub(super) get_active_uniforms_iv: Rc<Cell<Option<GetActiveUniformsIv>>>,
+    debug_message_callback: Rc<Cell<Option<debug::DebugMessageCallback>>>,
+    debug_callback: Rc<Cell<Option<&'static DebugCallbackSlot>>>,
     #[cfg(all(feature = "glutin", not(target_arch = "wasm32")))]
     pub(crate) glutin_context: Option<Rc<glutin::Context<glutin::PossiblyCurrent>>>,
 }
@@ -48,7 +59,18 @@ impl Context {
                 vao,
                 programs: Rc::new(RefCell::new(HashMap::new())),
                 effects: Rc::new(RefCell::new(HashMap::new())),
+                shader_chunks: Rc::new(RefCell::new(HashMap::new())),
+                #[cfg(not(target_arch = "wasm32"))]
+                shader_files: Rc::new(RefCell::new(HashMap::new())),
                 camera2d: Rc::new(RefCell::new(None)),
+                state_cache: Rc::new(RefCell::new(StateCache::new(true))),
+                resources: Rc::new(RefCell::new(ResourceRegistry::default())),
+                program_binary_cache: Rc::new(RefCell::new(ProgramBinaryCache::default())),
+                depth_test_without_depth_write: Rc::new(Cell::new(false)),
+                srgb_color_textures: Rc::new(Cell::new(true)),
+                get_active_uniforms_iv: Rc::new(Cell::new(None)),
+                debug_message_callback: Rc::new(Cell::new(None)),
+                debug_callback: Rc::new(Cell::new(None)),
                 #[cfg(all(feature = "glutin", not(target_arch = "wasm32")))]
                 glutin_context: None,
             }
@@ -95,6 +117,102 @@ impl Context {
         callback(self.effects.borrow().get(fragment_shader_source).unwrap())
     }
 
+    ///
+    /// Registers a shader chunk with the given name which can then be included in any shader source given to [Program::from_source]
+    /// (and therefore also in the source of a [Material](crate::renderer::Material), an [ImageEffect] etc.) using `#include "name"`.
+    /// A chunk registered with the same name as a chunk in the built-in library replaces the built-in chunk, see [Program::from_source] for the list of built-in chunks.
+    ///
+    /// **Note:** Programs that are already compiled, including the ones cached by [Context::program] and [Context::effect], are not affected.
+    ///
+    pub fn register_shader_chunk(&self, name: &str, source: &str) {
+        self.shader_chunks
+            .borrow_mut()
+            .insert(name.to_string(), source.to_string());
+    }
+
+    ///
+    /// Registers the content of the given file as a shader chunk with the given name, see [Context::register_shader_chunk].
+    /// The file is watched for changes by [Context::reload_shaders] which is meant for development, for example a program backed entirely by files on disk
+    /// is constructed by `Program::from_source(&context, "#include \"my_shader.vert\"", "#include \"my_shader.frag\"")`.
+    ///
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn register_shader_chunk_file(
+        &self,
+        name: &str,
+        path: impl AsRef<std::path::Path>,
+    ) -> ThreeDResult<()> {
+        let path = path.as_ref().to_path_buf();
+        let modified = std::fs::metadata(&path)?.modified()?;
+        self.register_shader_chunk(name, &std::fs::read_to_string(&path)?);
+        self.shader_files
+            .borrow_mut()
+            .insert(name.to_string(), (path, modified));
+        Ok(())
+    }
+
+    ///
+    /// Checks if any of the files registered using [Context::register_shader_chunk_file] has changed since last time and, in that case,
+    /// updates the shader chunks and compiles all programs cached by [Context::program] and [Context::effect] again (see [Program::reload]).
+    /// Call this at the beginning of each frame during development to see the changes to the shader files without restarting the application.
+    /// Returns true if any of the files has changed, which means that programs not cached in this context, for example a [Program] constructed directly, also needs to be reloaded to see the changes.
+    ///
+    /// # Errors
+    /// Returns an error if a file could not be read or if one of the cached programs failed to compile, typically [CoreError::ShaderCompilation].
+    /// In the latter case, the last working version of that program is kept, so the error can safely be reported and the rendering continued.
+    ///
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn reload_shaders(&self) -> ThreeDResult<bool> {
+        let mut changed = Vec::new();
+        for (name, (path, modified)) in self.shader_files.borrow().iter() {
+            let last_modified = std::fs::metadata(path)?.modified()?;
+            if last_modified != *modified {
+                changed.push((name.clone(), last_modified, std::fs::read_to_string(path)?));
+            }
+        }
+        if changed.is_empty() {
+            return Ok(false);
+        }
+        for (name, last_modified, source) in changed {
+            self.register_shader_chunk(&name, &source);
+            self.shader_files.borrow_mut().get_mut(&name).unwrap().1 = last_modified;
+        }
+        let mut result = Ok(());
+        for program in self.programs.borrow_mut().values_mut() {
+            if let Err(e) = program.reload() {
+                result = Err(e);
+            }
+        }
+        for effect in self.effects.borrow_mut().values_mut() {
+            if let Err(e) = effect.reload() {
+                result = Err(e);
+            }
+        }
+        result?;
+        Ok(true)
+    }
+
+    ///
+    /// Returns the source of the shader chunk with the given name, either registered using [Context::register_shader_chunk] or from the built-in library.
+    ///
+    pub fn shader_chunk(&self, name: &str) -> Option<String> {
+        self.shader_chunks
+            .borrow()
+            .get(name)
+            .cloned()
+            .or_else(|| builtin_shader_chunk(name).map(|s| s.to_string()))
+    }
+
+    ///
+    /// Returns whether or not GPU timer queries are supported, see [TimerQuery].
+    /// They are supported on desktop (`ARB_timer_query`) and on web and mobile if the `EXT_disjoint_timer_query` extension is available.
+    ///
+    pub fn supports_timer_queries(&self) -> bool {
+        !self.version().is_embedded
+            || self.supported_extensions().iter().any(|e| {
+                e.ends_with("disjoint_timer_query") || e.ends_with("disjoint_timer_query_webgl2")
+            })
+    }
+
     ///
     /// Returns a camera for viewing 2D content.
     ///
@@ -142,6 +260,13 @@ impl Context {
     /// Set the scissor test for this context (see [ScissorBox]).
     ///
     pub fn set_scissor(&self, scissor_box: ScissorBox) {
+        if !self
+            .state_cache
+            .borrow_mut()
+            .update(|s| &mut s.scissor_box, scissor_box)
+        {
+            return;
+        }
         unsafe {
             if scissor_box.width > 0 && scissor_box.height > 0 {
                 self.enable(crate::context::SCISSOR_TEST);
@@ -161,6 +286,13 @@ impl Context {
     /// Set the viewport for this context (See [Viewport]).
     ///
     pub fn set_viewport(&self, viewport: Viewport) {
+        if !self
+            .state_cache
+            .borrow_mut()
+            .update(|s| &mut s.viewport, viewport)
+        {
+            return;
+        }
         unsafe {
             self.viewport(
                 viewport.x,
@@ -175,6 +307,9 @@ impl Context {
     /// Set the face culling for this context (see [Cull]).
     ///
     pub fn set_cull(&self, cull: Cull) {
+        if !self.state_cache.borrow_mut().update(|s| &mut s.cull, cull) {
+            return;
+        }
         unsafe {
             match cull {
                 Cull::None => {
@@ -200,6 +335,13 @@ impl Context {
     /// Set the write mask for this context (see [WriteMask]).
     ///
     pub fn set_write_mask(&self, write_mask: WriteMask) {
+        if !self
+            .state_cache
+            .borrow_mut()
+            .update(|s| &mut s.write_mask, write_mask)
+        {
+            return;
+        }
         unsafe {
             self.color_mask(
                 write_mask.red,
@@ -215,6 +357,13 @@ impl Context {
     /// Set the depth test for this context (see [DepthTest]).
     ///
     pub fn set_depth_test(&self, depth_test: DepthTest) {
+        if !self
+            .state_cache
+            .borrow_mut()
+            .update(|s| &mut s.depth_test, Some(depth_test))
+        {
+            return;
+        }
         unsafe {
             self.enable(crate::context::DEPTH_TEST);
             match depth_test {
@@ -246,10 +395,104 @@ impl Context {
         }
     }
 
+    ///
+    /// Set the depth bias for this context (see [DepthBias]).
+    ///
+    pub fn set_depth_bias(&self, depth_bias: DepthBias) {
+        if !self
+            .state_cache
+            .borrow_mut()
+            .update(|s| &mut s.depth_bias, depth_bias)
+        {
+            return;
+        }
+        unsafe {
+            if depth_bias != DepthBias::NONE {
+                self.enable(crate::context::POLYGON_OFFSET_FILL);
+                self.polygon_offset(depth_bias.factor, depth_bias.units);
+            } else {
+                self.disable(crate::context::POLYGON_OFFSET_FILL);
+            }
+        }
+    }
+
+    ///
+    /// Set the stencil test for this context (see [StencilTest]).
+    ///
+    pub fn set_stencil_test(&self, stencil_test: StencilTest) {
+        if !self
+            .state_cache
+            .borrow_mut()
+            .update(|s| &mut s.stencil_test, stencil_test)
+        {
+            return;
+        }
+        unsafe {
+            if let StencilTest::Enabled {
+                function,
+                reference,
+                read_mask,
+                write_mask,
+                stencil_fail,
+                depth_fail,
+                depth_pass,
+            } = stencil_test
+            {
+                self.enable(crate::context::STENCIL_TEST);
+                self.stencil_func(
+                    Self::stencil_const_from_function(function),
+                    reference as i32,
+                    read_mask as u32,
+                );
+                self.stencil_op(
+                    Self::stencil_const_from_operation(stencil_fail),
+                    Self::stencil_const_from_operation(depth_fail),
+                    Self::stencil_const_from_operation(depth_pass),
+                );
+                self.stencil_mask(write_mask as u32);
+            } else {
+                self.disable(crate::context::STENCIL_TEST);
+            }
+        }
+    }
+
+    fn stencil_const_from_function(function: StencilFunction) -> u32 {
+        match function {
+            StencilFunction::Never => crate::context::NEVER,
+            StencilFunction::Less => crate::context::LESS,
+            StencilFunction::Equal => crate::context::EQUAL,
+            StencilFunction::LessOrEqual => crate::context::LEQUAL,
+            StencilFunction::Greater => crate::context::GREATER,
+            StencilFunction::NotEqual => crate::context::NOTEQUAL,
+            StencilFunction::GreaterOrEqual => crate::context::GEQUAL,
+            StencilFunction::Always => crate::context::ALWAYS,
+        }
+    }
+
+    fn stencil_const_from_operation(operation: StencilOperation) -> u32 {
+        match operation {
+            StencilOperation::Keep => crate::context::KEEP,
+            StencilOperation::Zero => crate::context::ZERO,
+            StencilOperation::Replace => crate::context::REPLACE,
+            StencilOperation::Increment => crate::context::INCR,
+            StencilOperation::IncrementWrap => crate::context::INCR_WRAP,
+            StencilOperation::Decrement => crate::context::DECR,
+            StencilOperation::DecrementWrap => crate::context::DECR_WRAP,
+            StencilOperation::Invert => crate::context::INVERT,
+        }
+    }
+
     ///
     /// Set the blend state for this context (see [Blend]).
     ///
     pub fn set_blend(&self, blend: Blend) {
+        if !self
+            .state_cache
+            .borrow_mut()
+            .update(|s| &mut s.blend, blend)
+        {
+            return;
+        }
         unsafe {
             if let Blend::Enabled {
                 source_rgb_multiplier,
@@ -308,15 +551,349 @@ impl Context {
     pub fn set_render_states(&self, render_states: RenderStates) -> ThreeDResult<()> {
         self.set_cull(render_states.cull);
         self.set_write_mask(render_states.write_mask);
-        if render_states.write_mask.depth {
+        if render_states.write_mask.depth || self.depth_test_without_depth_write.get() {
             self.set_depth_test(render_states.depth_test);
+            self.set_depth_bias(render_states.depth_bias);
         } else {
-            unsafe { self.disable(crate::context::DEPTH_TEST) }
+            if self
+                .state_cache
+                .borrow_mut()
+                .update(|s| &mut s.depth_test, None)
+            {
+                unsafe {
+                    self.disable(crate::context::DEPTH_TEST);
+                }
+            }
+            self.set_depth_bias(DepthBias::NONE);
         }
+        self.set_stencil_test(render_states.stencil_test);
         self.set_blend(render_states.blend);
         self.error_check()
     }
 
+    ///
+    /// Performs the depth test given in the render states while rendering whatever rendered in the `render` closure, even if writing to the depth buffer is disabled.
+    /// Otherwise, the depth test is disabled when writing to the depth buffer is disabled, see [Context::set_render_states].
+    /// This is used for proxy geometry which is tested against, but must not change, the depth buffer, for example the bounding boxes rendered by [OcclusionCulling](crate::renderer::OcclusionCulling).
+    ///
+    pub(crate) fn depth_test_without_depth_write(
+        &self,
+        render: impl FnOnce() -> ThreeDResult<()>,
+    ) -> ThreeDResult<()> {
+        self.depth_test_without_depth_write.set(true);
+        let result = render();
+        self.depth_test_without_depth_write.set(false);
+        result
+    }
+
+    ///
+    /// Specifies whether or not the albedo and emissive textures of the materials in the [renderer](crate::renderer) module, for example [PhysicalMaterial](crate::renderer::PhysicalMaterial),
+    /// are stored in sRGB color space (see [Texture2D::new_srgb]) when they are created from RGB or RGBA byte data. This is enabled by default.
+    /// A [CpuTexture] does not specify its color space, but byte data is in sRGB color space for most image formats.
+    /// When disabled, the textures are stored as they are and the conversion to linear color space is done in the shader after filtering, which uses less memory for RGB data but is less accurate.
+    ///
+    pub fn set_srgb_color_textures(&self, enabled: bool) {
+        self.srgb_color_textures.set(enabled);
+    }
+
+    ///
+    /// Returns whether or not the color textures of materials are stored in sRGB color space, see [Context::set_srgb_color_textures].
+    ///
+    pub fn srgb_color_textures(&self) -> bool {
+        self.srgb_color_textures.get()
+    }
+
+    ///
+    /// Returns a snapshot of the GPU resources created using this context which are still alive,
+    /// including the kind, an estimate of the used GPU memory and the debug label of each resource.
+    ///
+    pub fn resource_stats(&self) -> ResourceStats {
+        ResourceStats {
+            resources: self.resources.borrow().resources(),
+            cached_programs: self.programs.borrow().len(),
+            cached_effects: self.effects.borrow().len(),
+        }
+    }
+
+    ///
+    /// Loads the graphics functions which are not available in the low-level graphics context using the given function which returns the address of a graphics function given its name.
+    /// These functions are used by the program binary cache (see [Context::set_program_binary_cache]), to get the layout of uniform blocks (see [Program::active_uniform_blocks])
+    /// and to receive debug messages (see [Context::set_debug_callback]).
+    /// This is done automatically when the context is created using the [window](crate::window) module,
+    /// so it should only be called if you are creating the low-level context yourself, in which case it should be called with the same loader function as the one given to the low-level context.
+    ///
+    /// # Safety
+    /// The loader function must return either a null pointer or the address of the graphics function with the given name in this context.
+    ///
+    pub unsafe fn load_additional_functions(
+        &self,
+        mut loader: impl FnMut(&str) -> *const std::ffi::c_void,
+    ) {
+        self.program_binary_cache
+            .borrow_mut()
+            .load_functions(&mut loader);
+        let get_active_uniforms_iv = loader("glGetActiveUniformsiv");
+        self.get_active_uniforms_iv
+            .set(if get_active_uniforms_iv.is_null() {
+                None
+            } else {
+                Some(std::mem::transmute::<
+                    *const std::ffi::c_void,
+                    GetActiveUniformsIv,
+                >(get_active_uniforms_iv))
+            });
+        let mut debug_message_callback = loader("glDebugMessageCallback");
+        if debug_message_callback.is_null() {
+            debug_message_callback = loader("glDebugMessageCallbackKHR");
+        }
+        self.debug_message_callback
+            .set(if debug_message_callback.is_null() {
+                None
+            } else {
+                Some(std::mem::transmute::<
+                    *const std::ffi::c_void,
+                    debug::DebugMessageCallback,
+                >(debug_message_callback))
+            });
+    }
+
+    ///
+    /// Returns whether or not program binaries are supported, which is required by [Context::set_program_binary_cache].
+    /// Program binaries are available on desktop if the graphics driver supports at least one binary format and the functions are loaded (see [Context::load_additional_functions]), but not on web.
+    ///
+    pub fn supports_program_binaries(&self) -> bool {
+        self.program_binary_cache.borrow().is_supported()
+            && unsafe { self.get_parameter_i32(crate::context::NUM_PROGRAM_BINARY_FORMATS) } > 0
+    }
+
+    ///
+    /// Stores the binary of every [Program] compiled from now on in the given directory, including the programs compiled by [Context::program], materials and effects,
+    /// and loads the stored binary instead of compiling the program the next time a program is created from the same source.
+    /// The binaries are stored per graphics driver, so a binary is not used if the application is run on another GPU or with another driver version.
+    /// A stored binary which is rejected by the graphics driver, for example after a driver update, is silently ignored and the program is compiled instead.
+    ///
+    /// # Errors
+    /// Returns an error if program binaries are not supported, see [Context::supports_program_binaries], or if the directory cannot be created.
+    ///
+    pub fn set_program_binary_cache(
+        &self,
+        directory: impl AsRef<std::path::Path>,
+    ) -> ThreeDResult<()> {
+        if !self.supports_program_binaries() {
+            Err(CoreError::ProgramBinaryNotSupported)?;
+        }
+        std::fs::create_dir_all(directory.as_ref())?;
+        let driver = unsafe {
+            [
+                crate::context::VENDOR,
+                crate::context::RENDERER,
+                crate::context::VERSION,
+                crate::context::SHADING_LANGUAGE_VERSION,
+            ]
+            .iter()
+            .map(|parameter| self.get_parameter_string(*parameter))
+            .collect::<Vec<_>>()
+            .join("\n")
+        };
+        self.program_binary_cache.borrow_mut().set_directory(
+            self,
+            directory.as_ref().to_path_buf(),
+            driver,
+        )
+    }
+
+    ///
+    /// Returns whether or not debug output is supported, which is required by [Context::set_debug_callback].
+    /// Debug output is available on desktop if the `KHR_debug` extension is supported, but not on web.
+    ///
+    pub fn supports_debug_output(&self) -> bool {
+        self.supports_debug()
+    }
+
+    ///
+    /// Routes the messages from the graphics driver, for example errors, performance warnings and the debug groups pushed using [Context::debug_group], to the given callback.
+    /// The messages are reported synchronously, so the callback is called from within the graphics call that caused the message.
+    /// The callback replaces any callback previously set for this context, and it can make graphics calls or set a new callback itself.
+    ///
+    /// # Errors
+    /// Returns an error if debug output is not supported, see [Context::supports_debug_output],
+    /// or if the graphics function which sets the callback is not loaded, see [Context::load_additional_functions].
+    ///
+    pub fn set_debug_callback(
+        &self,
+        callback: impl Fn(DebugMessage) + Send + 'static,
+    ) -> ThreeDResult<()> {
+        let debug_message_callback = match self.debug_message_callback.get() {
+            Some(debug_message_callback) if self.supports_debug_output() => debug_message_callback,
+            _ => Err(CoreError::DebugOutputNotSupported)?,
+        };
+        let slot = self
+            .debug_callback
+            .get()
+            .unwrap_or_else(DebugCallbackSlot::new);
+        self.debug_callback.set(Some(slot));
+        slot.set(std::sync::Arc::new(callback));
+        unsafe {
+            self.enable(crate::context::DEBUG_OUTPUT);
+            self.enable(crate::context::DEBUG_OUTPUT_SYNCHRONOUS);
+            debug_message_callback(
+                Some(debug::handle_debug_message),
+                slot as *const DebugCallbackSlot as *const std::ffi::c_void,
+            );
+        }
+        self.error_check()
+    }
+
+    ///
+    /// Pushes a debug group with the given name before calling the callback and pops it afterwards,
+    /// so that the graphics calls issued in the callback are grouped together when inspected in a GPU debugger.
+    /// Debug groups can be nested. Only the callback is called if debug output is not supported, see [Context::supports_debug_output].
+    ///
+    pub fn debug_group<T>(
+        &self,
+        name: &str,
+        callback: impl FnOnce() -> ThreeDResult<T>,
+    ) -> ThreeDResult<T> {
+        if !self.supports_debug_output() {
+            return callback();
+        }
+        unsafe {
+            self.push_debug_group(crate::context::DEBUG_SOURCE_APPLICATION, 0, name);
+        }
+        let result = callback();
+        unsafe {
+            self.pop_debug_group();
+        }
+        result
+    }
+
+    ///
+    /// Gives the object bound by the `bind` closure the given label in the graphics driver if debug output is supported,
+    /// where `binding` is the parameter used to look up the name of the bound object.
+    ///
+    pub(super) fn label_object(
+        &self,
+        label: &str,
+        identifier: u32,
+        binding: u32,
+        bind: impl FnOnce(),
+    ) {
+        if self.supports_debug_output() {
+            bind();
+            unsafe {
+                let name = self.get_parameter_i32(binding) as u32;
+                self.object_label(identifier, name, Some(label));
+            }
+        }
+    }
+
+    pub(super) fn track_resource(&self, kind: ResourceKind, byte_size: usize) -> ResourceHandle {
+        ResourceRegistry::track(&self.resources, kind, byte_size)
+    }
+
+    ///
+    /// Enables or disables the cache of the graphics state which is used to skip redundant state changes,
+    /// for example when setting the same render states, binding the same program or binding the same textures as in the previous draw call.
+    /// The cache is enabled by default.
+    ///
+    /// The cache assumes that all state changes go through this context, so if you also change the state using the low-level graphics context (see the [context](crate::context) module),
+    /// either disable the cache or call [Context::invalidate_state_cache] after the low-level calls.
+    /// Programs and vertex arrays are always unbound after use, so they can be bound using the low-level graphics context without invalidating the cache.
+    ///
+    pub fn set_state_cache_enabled(&self, enabled: bool) {
+        *self.state_cache.borrow_mut() = StateCache::new(enabled);
+    }
+
+    ///
+    /// Returns whether or not the cache of the graphics state is enabled, see [Context::set_state_cache_enabled].
+    ///
+    pub fn is_state_cache_enabled(&self) -> bool {
+        self.state_cache.borrow().enabled
+    }
+
+    ///
+    /// Forgets the cached graphics state so that the next state changes are sent to the graphics driver.
+    /// Call this after changing the state using the low-level graphics context while the cache is enabled, see [Context::set_state_cache_enabled].
+    ///
+    pub fn invalidate_state_cache(&self) {
+        let enabled = self.is_state_cache_enabled();
+        *self.state_cache.borrow_mut() = StateCache::new(enabled);
+    }
+
+    pub(super) fn use_program_cached(&self, program: Option<crate::context::Program>) {
+        if self
+            .state_cache
+            .borrow_mut()
+            .update(|s| &mut s.program, program)
+        {
+            unsafe {
+                self.use_program(program);
+            }
+        }
+    }
+
+    pub(super) fn use_vertex_array(&self) {
+        self.bind_vertex_array_cached(Some(self.vao));
+    }
+
+    pub(super) fn unuse_vertex_array(&self) {
+        self.bind_vertex_array_cached(None);
+    }
+
+    fn bind_vertex_array_cached(&self, vao: Option<crate::context::VertexArray>) {
+        if self
+            .state_cache
+            .borrow_mut()
+            .update(|s| &mut s.vertex_array, vao)
+        {
+            unsafe {
+                self.bind_vertex_array(vao);
+            }
+        }
+    }
+
+    pub(super) fn active_texture_cached(&self, unit: u32) {
+        if self
+            .state_cache
+            .borrow_mut()
+            .update(|s| &mut s.active_texture, unit)
+        {
+            unsafe {
+                self.active_texture(crate::context::TEXTURE0 + unit);
+            }
+        }
+    }
+
+    pub(super) fn bind_texture_cached(&self, target: u32, texture: crate::context::Texture) {
+        let mut cache = self.state_cache.borrow_mut();
+        let bound = match cache.active_texture {
+            Some(unit) if cache.enabled => {
+                cache.textures.insert((unit, target), texture) != Some(texture)
+            }
+            _ => true,
+        };
+        drop(cache);
+        if bound {
+            unsafe {
+                self.bind_texture(target, Some(texture));
+            }
+        }
+    }
+
+    pub(super) fn forget_texture(&self, texture: crate::context::Texture) {
+        self.state_cache
+            .borrow_mut()
+            .textures
+            .retain(|_, t| *t != texture);
+    }
+
+    pub(super) fn forget_program(&self, program: crate::context::Program) {
+        let mut cache = self.state_cache.borrow_mut();
+        if cache.program == Some(Some(program)) {
+            cache.program = None;
+        }
+    }
+
     pub(super) fn error_check(&self) -> ThreeDResult<()> {
         #[cfg(debug_assertions)]
         unsafe {
@@ -407,3 +984,53 @@ impl std::ops::Deref for Context {
         &self.context
     }
 }
+
+///
+/// A copy of the parts of the graphics state that is set through the [Context], see [Context::set_state_cache_enabled].
+/// `None` means that the state is unknown.
+///
+#[derive(Default)]
+pub(super) struct StateCache {
+    enabled: bool,
+    scissor_box: Option<ScissorBox>,
+    viewport: Option<Viewport>,
+    cull: Option<Cull>,
+    write_mask: Option<WriteMask>,
+    depth_test: Option<Option<DepthTest>>,
+    depth_bias: Option<DepthBias>,
+    pub(super) stencil_test: Option<StencilTest>,
+    blend: Option<Blend>,
+    program: Option<Option<crate::context::Program>>,
+    vertex_array: Option<Option<crate::context::VertexArray>>,
+    active_texture: Option<u32>,
+    textures: HashMap<(u32, u32), crate::context::Texture>,
+}
+
+impl StateCache {
+    fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            ..Default::default()
+        }
+    }
+
+    ///
+    /// Updates the cached value and returns whether or not the value has changed and therefore needs to be sent to the graphics driver.
+    ///
+    fn update<T: PartialEq>(
+        &mut self,
+        field: impl FnOnce(&mut Self) -> &mut Option<T>,
+        value: T,
+    ) -> bool {
+        if !self.enabled {
+            return true;
+        }
+        let cached = field(self);
+        if cached.as_ref() == Some(&value) {
+            false
+        } else {
+            *cached = Some(value);
+            true
+        }
+    }
+}
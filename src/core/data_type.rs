@@ -18,10 +18,15 @@ pub trait PrimitiveDataType: DataType + Copy + Default {
         data: &[Self],
         type_: UniformType,
     );
+    fn uniform_data_type_with_type(type_: UniformType) -> u32;
     fn internal_format_with_size(size: u32) -> u32;
 }
 
 impl PrimitiveDataType for u8 {
+    fn uniform_data_type_with_type(type_: UniformType) -> u32 {
+        u32::uniform_data_type_with_type(type_)
+    }
+
     fn internal_format_with_size(size: u32) -> u32 {
         match size {
             1 => crate::context::R8,
@@ -43,6 +48,10 @@ impl PrimitiveDataType for u8 {
     }
 }
 impl PrimitiveDataType for u16 {
+    fn uniform_data_type_with_type(type_: UniformType) -> u32 {
+        u32::uniform_data_type_with_type(type_)
+    }
+
     fn internal_format_with_size(size: u32) -> u32 {
         match size {
             1 => crate::context::R16UI,
@@ -64,6 +73,16 @@ impl PrimitiveDataType for u16 {
     }
 }
 impl PrimitiveDataType for u32 {
+    fn uniform_data_type_with_type(type_: UniformType) -> u32 {
+        match type_ {
+            UniformType::Value => crate::context::UNSIGNED_INT,
+            UniformType::Vec2 => crate::context::UNSIGNED_INT_VEC2,
+            UniformType::Vec3 => crate::context::UNSIGNED_INT_VEC3,
+            UniformType::Vec4 => crate::context::UNSIGNED_INT_VEC4,
+            _ => unimplemented!(),
+        }
+    }
+
     fn internal_format_with_size(size: u32) -> u32 {
         match size {
             1 => crate::context::R32UI,
@@ -92,6 +111,10 @@ impl PrimitiveDataType for u32 {
     }
 }
 impl PrimitiveDataType for i8 {
+    fn uniform_data_type_with_type(type_: UniformType) -> u32 {
+        i32::uniform_data_type_with_type(type_)
+    }
+
     fn internal_format_with_size(size: u32) -> u32 {
         match size {
             1 => crate::context::R8I,
@@ -113,6 +136,10 @@ impl PrimitiveDataType for i8 {
     }
 }
 impl PrimitiveDataType for i16 {
+    fn uniform_data_type_with_type(type_: UniformType) -> u32 {
+        i32::uniform_data_type_with_type(type_)
+    }
+
     fn internal_format_with_size(size: u32) -> u32 {
         match size {
             1 => crate::context::R16I,
@@ -134,6 +161,16 @@ impl PrimitiveDataType for i16 {
     }
 }
 impl PrimitiveDataType for i32 {
+    fn uniform_data_type_with_type(type_: UniformType) -> u32 {
+        match type_ {
+            UniformType::Value => crate::context::INT,
+            UniformType::Vec2 => crate::context::INT_VEC2,
+            UniformType::Vec3 => crate::context::INT_VEC3,
+            UniformType::Vec4 => crate::context::INT_VEC4,
+            _ => unimplemented!(),
+        }
+    }
+
     fn internal_format_with_size(size: u32) -> u32 {
         match size {
             1 => crate::context::R32I,
@@ -162,6 +199,10 @@ impl PrimitiveDataType for i32 {
     }
 }
 impl PrimitiveDataType for f16 {
+    fn uniform_data_type_with_type(type_: UniformType) -> u32 {
+        f32::uniform_data_type_with_type(type_)
+    }
+
     fn internal_format_with_size(size: u32) -> u32 {
         match size {
             1 => crate::context::R16F,
@@ -183,6 +224,18 @@ impl PrimitiveDataType for f16 {
     }
 }
 impl PrimitiveDataType for f32 {
+    fn uniform_data_type_with_type(type_: UniformType) -> u32 {
+        match type_ {
+            UniformType::Value => crate::context::FLOAT,
+            UniformType::Vec2 => crate::context::FLOAT_VEC2,
+            UniformType::Vec3 => crate::context::FLOAT_VEC3,
+            UniformType::Vec4 => crate::context::FLOAT_VEC4,
+            UniformType::Mat2 => crate::context::FLOAT_MAT2,
+            UniformType::Mat3 => crate::context::FLOAT_MAT3,
+            UniformType::Mat4 => crate::context::FLOAT_MAT4,
+        }
+    }
+
     fn internal_format_with_size(size: u32) -> u32 {
         match size {
             1 => crate::context::R32F,
@@ -224,6 +277,7 @@ pub trait DataType: std::fmt::Debug + Clone {
     fn data_type() -> u32;
     fn size() -> u32;
     fn send_uniform(context: &Context, location: &UniformLocation, data: &[Self]);
+    fn uniform_data_type() -> u32;
 }
 
 impl<T: DataType + ?Sized> DataType for &T {
@@ -244,6 +298,10 @@ impl<T: DataType + ?Sized> DataType for &T {
             &data.iter().map(|v| (*v).clone()).collect::<Vec<_>>(),
         )
     }
+
+    fn uniform_data_type() -> u32 {
+        T::uniform_data_type()
+    }
 }
 
 impl DataType for u8 {
@@ -262,6 +320,10 @@ impl DataType for u8 {
     fn send_uniform(context: &Context, location: &UniformLocation, data: &[Self]) {
         Self::send_uniform_with_type(context, location, data, UniformType::Value)
     }
+
+    fn uniform_data_type() -> u32 {
+        Self::uniform_data_type_with_type(UniformType::Value)
+    }
 }
 
 impl DataType for u16 {
@@ -279,6 +341,10 @@ impl DataType for u16 {
     fn send_uniform(context: &Context, location: &UniformLocation, data: &[Self]) {
         Self::send_uniform_with_type(context, location, data, UniformType::Value)
     }
+
+    fn uniform_data_type() -> u32 {
+        Self::uniform_data_type_with_type(UniformType::Value)
+    }
 }
 
 impl DataType for u32 {
@@ -297,6 +363,10 @@ impl DataType for u32 {
     fn send_uniform(context: &Context, location: &UniformLocation, data: &[Self]) {
         Self::send_uniform_with_type(context, location, data, UniformType::Value)
     }
+
+    fn uniform_data_type() -> u32 {
+        Self::uniform_data_type_with_type(UniformType::Value)
+    }
 }
 
 impl DataType for i8 {
@@ -315,6 +385,10 @@ impl DataType for i8 {
     fn send_uniform(context: &Context, location: &UniformLocation, data: &[Self]) {
         Self::send_uniform_with_type(context, location, data, UniformType::Value)
     }
+
+    fn uniform_data_type() -> u32 {
+        Self::uniform_data_type_with_type(UniformType::Value)
+    }
 }
 
 impl DataType for i16 {
@@ -333,6 +407,10 @@ impl DataType for i16 {
     fn send_uniform(context: &Context, location: &UniformLocation, data: &[Self]) {
         Self::send_uniform_with_type(context, location, data, UniformType::Value)
     }
+
+    fn uniform_data_type() -> u32 {
+        Self::uniform_data_type_with_type(UniformType::Value)
+    }
 }
 
 impl DataType for i32 {
@@ -351,6 +429,10 @@ impl DataType for i32 {
     fn send_uniform(context: &Context, location: &UniformLocation, data: &[Self]) {
         Self::send_uniform_with_type(context, location, data, UniformType::Value)
     }
+
+    fn uniform_data_type() -> u32 {
+        Self::uniform_data_type_with_type(UniformType::Value)
+    }
 }
 
 impl DataType for f16 {
@@ -368,6 +450,10 @@ impl DataType for f16 {
     fn send_uniform(context: &Context, location: &UniformLocation, data: &[Self]) {
         Self::send_uniform_with_type(context, location, data, UniformType::Value)
     }
+
+    fn uniform_data_type() -> u32 {
+        Self::uniform_data_type_with_type(UniformType::Value)
+    }
 }
 
 impl DataType for f32 {
@@ -386,6 +472,10 @@ impl DataType for f32 {
     fn send_uniform(context: &Context, location: &UniformLocation, data: &[Self]) {
         Self::send_uniform_with_type(context, location, data, UniformType::Value)
     }
+
+    fn uniform_data_type() -> u32 {
+        Self::uniform_data_type_with_type(UniformType::Value)
+    }
 }
 
 impl<T: PrimitiveDataType> DataType for Vector2<T> {
@@ -405,6 +495,10 @@ impl<T: PrimitiveDataType> DataType for Vector2<T> {
         let data = data.iter().flat_map(|v| [v.x, v.y]).collect::<Vec<_>>();
         T::send_uniform_with_type(context, location, &data, UniformType::Vec2)
     }
+
+    fn uniform_data_type() -> u32 {
+        T::uniform_data_type_with_type(UniformType::Vec2)
+    }
 }
 
 impl<T: PrimitiveDataType> DataType for [T; 2] {
@@ -424,6 +518,10 @@ impl<T: PrimitiveDataType> DataType for [T; 2] {
         let data = data.iter().flatten().map(|v| *v).collect::<Vec<_>>();
         T::send_uniform_with_type(context, location, &data, UniformType::Vec2)
     }
+
+    fn uniform_data_type() -> u32 {
+        T::uniform_data_type_with_type(UniformType::Vec2)
+    }
 }
 
 impl<T: PrimitiveDataType> DataType for Vector3<T> {
@@ -445,6 +543,10 @@ impl<T: PrimitiveDataType> DataType for Vector3<T> {
             .collect::<Vec<_>>();
         T::send_uniform_with_type(context, location, &data, UniformType::Vec3)
     }
+
+    fn uniform_data_type() -> u32 {
+        T::uniform_data_type_with_type(UniformType::Vec3)
+    }
 }
 
 impl<T: PrimitiveDataType> DataType for [T; 3] {
@@ -463,6 +565,10 @@ impl<T: PrimitiveDataType> DataType for [T; 3] {
         let data = data.iter().flatten().map(|v| *v).collect::<Vec<_>>();
         T::send_uniform_with_type(context, location, &data, UniformType::Vec3)
     }
+
+    fn uniform_data_type() -> u32 {
+        T::uniform_data_type_with_type(UniformType::Vec3)
+    }
 }
 
 impl<T: PrimitiveDataType> DataType for Vector4<T> {
@@ -485,6 +591,10 @@ impl<T: PrimitiveDataType> DataType for Vector4<T> {
             .collect::<Vec<_>>();
         T::send_uniform_with_type(context, location, &data, UniformType::Vec4)
     }
+
+    fn uniform_data_type() -> u32 {
+        T::uniform_data_type_with_type(UniformType::Vec4)
+    }
 }
 
 impl<T: PrimitiveDataType> DataType for [T; 4] {
@@ -504,6 +614,10 @@ impl<T: PrimitiveDataType> DataType for [T; 4] {
         let data = data.iter().flatten().map(|v| *v).collect::<Vec<_>>();
         T::send_uniform_with_type(context, location, &data, UniformType::Vec4)
     }
+
+    fn uniform_data_type() -> u32 {
+        T::uniform_data_type_with_type(UniformType::Vec4)
+    }
 }
 
 impl<T: PrimitiveDataType> DataType for Quaternion<T> {
@@ -526,6 +640,10 @@ impl<T: PrimitiveDataType> DataType for Quaternion<T> {
             .collect::<Vec<_>>();
         T::send_uniform_with_type(context, location, &data, UniformType::Vec4)
     }
+
+    fn uniform_data_type() -> u32 {
+        T::uniform_data_type_with_type(UniformType::Vec4)
+    }
 }
 
 impl DataType for Color {
@@ -555,6 +673,10 @@ impl DataType for Color {
             .collect::<Vec<_>>();
         f32::send_uniform_with_type(context, location, &data, UniformType::Vec4)
     }
+
+    fn uniform_data_type() -> u32 {
+        f32::uniform_data_type_with_type(UniformType::Vec4)
+    }
 }
 
 impl<T: PrimitiveDataType> DataType for Matrix2<T> {
@@ -577,6 +699,10 @@ impl<T: PrimitiveDataType> DataType for Matrix2<T> {
             .collect::<Vec<_>>();
         T::send_uniform_with_type(context, location, &data, UniformType::Mat2)
     }
+
+    fn uniform_data_type() -> u32 {
+        T::uniform_data_type_with_type(UniformType::Mat2)
+    }
 }
 
 impl<T: PrimitiveDataType> DataType for Matrix3<T> {
@@ -603,6 +729,10 @@ impl<T: PrimitiveDataType> DataType for Matrix3<T> {
             .collect::<Vec<_>>();
         T::send_uniform_with_type(context, location, &data, UniformType::Mat3)
     }
+
+    fn uniform_data_type() -> u32 {
+        T::uniform_data_type_with_type(UniformType::Mat3)
+    }
 }
 
 impl<T: PrimitiveDataType> DataType for Matrix4<T> {
@@ -630,4 +760,8 @@ impl<T: PrimitiveDataType> DataType for Matrix4<T> {
             .collect::<Vec<_>>();
         T::send_uniform_with_type(context, location, &data, UniformType::Mat4)
     }
+
+    fn uniform_data_type() -> u32 {
+        T::uniform_data_type_with_type(UniformType::Mat4)
+    }
 }
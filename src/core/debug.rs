@@ -0,0 +1,165 @@
+use std::ffi::{c_void, CStr};
+use std::os::raw::c_char;
+use std::sync::{Arc, Mutex};
+
+///
+/// The source of a [DebugMessage].
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DebugSource {
+    /// Calls to the graphics API.
+    Api,
+    /// The window system.
+    WindowSystem,
+    /// The shader compiler.
+    ShaderCompiler,
+    /// A third party application, for example a GPU debugger.
+    ThirdParty,
+    /// This application.
+    Application,
+    /// Any other source.
+    Other,
+}
+
+///
+/// The type of a [DebugMessage].
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DebugMessageType {
+    /// An error, typically the same as the errors reported as [CoreError::ContextError](crate::core::CoreError::ContextError).
+    Error,
+    /// Use of deprecated functionality.
+    DeprecatedBehavior,
+    /// Use of functionality with undefined behavior.
+    UndefinedBehavior,
+    /// Use of functionality which is not portable.
+    Portability,
+    /// A potential performance issue.
+    Performance,
+    /// A marker inserted in the command stream.
+    Marker,
+    /// A debug group has been pushed, see [Context::debug_group](crate::core::Context::debug_group).
+    PushGroup,
+    /// A debug group has been popped, see [Context::debug_group](crate::core::Context::debug_group).
+    PopGroup,
+    /// Any other type of message.
+    Other,
+}
+
+///
+/// The severity of a [DebugMessage].
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum DebugSeverity {
+    /// Purely informational messages.
+    Notification,
+    /// Minor performance issues or redundant state changes.
+    Low,
+    /// Major performance issues or use of deprecated functionality.
+    Medium,
+    /// Errors or undefined behavior.
+    High,
+}
+
+///
+/// A message from the graphics driver, see [Context::set_debug_callback](crate::core::Context::set_debug_callback).
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DebugMessage {
+    /// The source of the message.
+    pub source: DebugSource,
+    /// The type of the message.
+    pub message_type: DebugMessageType,
+    /// An identifier of the message which is specific to the graphics driver.
+    pub id: u32,
+    /// The severity of the message.
+    pub severity: DebugSeverity,
+    /// The message.
+    pub message: String,
+}
+
+type DebugCallback = Arc<dyn Fn(DebugMessage) + Send>;
+
+///
+/// The callback set for a context using [Context::set_debug_callback](crate::core::Context::set_debug_callback),
+/// which is given to the graphics driver as the user parameter of [handle_debug_message].
+///
+pub(super) struct DebugCallbackSlot(Mutex<Option<DebugCallback>>);
+
+impl DebugCallbackSlot {
+    pub(super) fn new() -> &'static Self {
+        // The graphics driver can call the debug message callback as long as the low-level graphics context is alive,
+        // which might be longer than the context, so the slot is never freed.
+        Box::leak(Box::new(Self(Mutex::new(None))))
+    }
+
+    pub(super) fn set(&self, callback: DebugCallback) {
+        *self.0.lock().unwrap() = Some(callback);
+    }
+}
+
+pub(super) type DebugProc = extern "system" fn(u32, u32, u32, u32, i32, *const c_char, *mut c_void);
+pub(super) type DebugMessageCallback = unsafe extern "system" fn(Option<DebugProc>, *const c_void);
+
+pub(super) extern "system" fn handle_debug_message(
+    source: u32,
+    message_type: u32,
+    id: u32,
+    severity: u32,
+    length: i32,
+    message: *const c_char,
+    slot: *mut c_void,
+) {
+    let slot = unsafe { &*(slot as *const DebugCallbackSlot) };
+    // The lock is released before the callback is called, so the callback can make graphics calls or set a new callback.
+    let callback = match slot.0.lock() {
+        Ok(callback) => callback.clone(),
+        Err(_) => return,
+    };
+    if let Some(callback) = callback {
+        let message = unsafe {
+            if length < 0 {
+                CStr::from_ptr(message).to_string_lossy().into_owned()
+            } else {
+                String::from_utf8_lossy(std::slice::from_raw_parts(
+                    message as *const u8,
+                    length as usize,
+                ))
+                .into_owned()
+            }
+        };
+        callback(DebugMessage {
+            source: match source {
+                crate::context::DEBUG_SOURCE_API => DebugSource::Api,
+                crate::context::DEBUG_SOURCE_WINDOW_SYSTEM => DebugSource::WindowSystem,
+                crate::context::DEBUG_SOURCE_SHADER_COMPILER => DebugSource::ShaderCompiler,
+                crate::context::DEBUG_SOURCE_THIRD_PARTY => DebugSource::ThirdParty,
+                crate::context::DEBUG_SOURCE_APPLICATION => DebugSource::Application,
+                _ => DebugSource::Other,
+            },
+            message_type: match message_type {
+                crate::context::DEBUG_TYPE_ERROR => DebugMessageType::Error,
+                crate::context::DEBUG_TYPE_DEPRECATED_BEHAVIOR => {
+                    DebugMessageType::DeprecatedBehavior
+                }
+                crate::context::DEBUG_TYPE_UNDEFINED_BEHAVIOR => {
+                    DebugMessageType::UndefinedBehavior
+                }
+                crate::context::DEBUG_TYPE_PORTABILITY => DebugMessageType::Portability,
+                crate::context::DEBUG_TYPE_PERFORMANCE => DebugMessageType::Performance,
+                crate::context::DEBUG_TYPE_MARKER => DebugMessageType::Marker,
+                crate::context::DEBUG_TYPE_PUSH_GROUP => DebugMessageType::PushGroup,
+                crate::context::DEBUG_TYPE_POP_GROUP => DebugMessageType::PopGroup,
+                _ => DebugMessageType::Other,
+            },
+            id,
+            severity: match severity {
+                crate::context::DEBUG_SEVERITY_HIGH => DebugSeverity::High,
+                crate::context::DEBUG_SEVERITY_MEDIUM => DebugSeverity::Medium,
+                crate::context::DEBUG_SEVERITY_LOW => DebugSeverity::Low,
+                _ => DebugSeverity::Notification,
+            },
+            message,
+        });
+    }
+}
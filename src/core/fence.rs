@@ -0,0 +1,74 @@
+use crate::core::*;
+use std::time::Duration;
+
+///
+/// A synchronization point inserted into the stream of commands sent to the GPU.
+/// The fence is signaled when the GPU has finished executing all commands issued before the fence was created,
+/// which can be used to find out when the result of asynchronous work, for example a [PixelReadback], is available
+/// or to limit the number of frames the CPU is ahead of the GPU.
+///
+pub struct Fence {
+    context: Context,
+    id: crate::context::Fence,
+}
+
+impl Fence {
+    ///
+    /// Inserts a new fence after all commands issued so far.
+    ///
+    pub fn new(context: &Context) -> ThreeDResult<Self> {
+        let id = unsafe {
+            context
+                .fence_sync(crate::context::SYNC_GPU_COMMANDS_COMPLETE, 0)
+                .map_err(CoreError::FenceCreation)?
+        };
+        // Make sure the commands are sent to the GPU, otherwise the fence might never be signaled.
+        unsafe { context.flush() };
+        context.error_check()?;
+        Ok(Self {
+            context: context.clone(),
+            id,
+        })
+    }
+
+    ///
+    /// Returns whether or not the GPU has finished executing all commands issued before this fence was created.
+    /// Does not block.
+    ///
+    pub fn is_signaled(&self) -> bool {
+        unsafe { self.context.get_sync_status(self.id) == crate::context::SIGNALED }
+    }
+
+    ///
+    /// Blocks until the fence is signaled or the timeout has expired and returns whether or not the fence is signaled.
+    /// The timeout is limited to a little more than two seconds.
+    ///
+    /// **Note:** On web, it is not possible to block, so the timeout is ignored and this is the same as [Fence::is_signaled].
+    ///
+    pub fn wait(&self, timeout: Duration) -> ThreeDResult<bool> {
+        #[cfg(not(target_arch = "wasm32"))]
+        let timeout = timeout.as_nanos().min(i32::MAX as u128) as i32;
+        #[cfg(target_arch = "wasm32")]
+        let timeout = {
+            let _ = timeout;
+            0
+        };
+        let status = unsafe {
+            self.context
+                .client_wait_sync(self.id, crate::context::SYNC_FLUSH_COMMANDS_BIT, timeout)
+        };
+        match status {
+            crate::context::ALREADY_SIGNALED | crate::context::CONDITION_SATISFIED => Ok(true),
+            crate::context::TIMEOUT_EXPIRED => Ok(false),
+            _ => Err(CoreError::FenceWait)?,
+        }
+    }
+}
+
+impl Drop for Fence {
+    fn drop(&mut self) {
+        unsafe {
+            self.context.delete_sync(self.id);
+        }
+    }
+}
@@ -49,6 +49,13 @@ impl ImageEffect {
         })
     }
 
+    ///
+    /// Compiles the program of this effect again, see [Program::reload].
+    ///
+    pub fn reload(&mut self) -> ThreeDResult<()> {
+        self.program.reload()
+    }
+
     ///
     /// Get the texture transform applied to the uv coordinates of the image effect.
     ///
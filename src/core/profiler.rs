@@ -0,0 +1,173 @@
+use crate::core::*;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+///
+/// The average time spent in a named scope measured by a [Profiler], see [Profiler::report].
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProfilerScope {
+    /// The name of the scope.
+    pub name: String,
+    /// The average time the CPU spent issuing the commands in the scope.
+    pub cpu_time: Duration,
+    /// The average time the GPU spent executing the commands issued in the scope or `None` if no GPU measurements are available yet or timer queries are not supported.
+    pub gpu_time: Option<Duration>,
+}
+
+struct Samples {
+    name: String,
+    cpu: VecDeque<Duration>,
+    gpu: VecDeque<Duration>,
+}
+
+///
+/// A lightweight profiler which measures the average CPU and GPU time spent in named scopes,
+/// for example around calls to [RenderTarget::write], the generation of shadow maps or the construction of an environment.
+/// The GPU time is measured using a [TimerQuery] if timer queries are supported (see [Context::supports_timer_queries])
+/// and since the GPU executes the commands asynchronously, the GPU times are only included in the report one or more frames later.
+///
+/// **Note:** Do not use a [TimerQuery] inside a scope, since only one timer query can be active at a time.
+///
+pub struct Profiler {
+    context: Context,
+    supports_timer_queries: bool,
+    number_of_samples: usize,
+    scopes: Vec<Samples>,
+    pending_queries: Vec<(usize, TimerQuery)>,
+    free_queries: Vec<TimerQuery>,
+}
+
+impl Profiler {
+    ///
+    /// Creates a new profiler which reports the average of the last `number_of_samples` measurements of each scope.
+    ///
+    pub fn new(context: &Context, number_of_samples: usize) -> Self {
+        Self {
+            context: context.clone(),
+            supports_timer_queries: context.supports_timer_queries(),
+            number_of_samples: number_of_samples.max(1),
+            scopes: Vec::new(),
+            pending_queries: Vec::new(),
+            free_queries: Vec::new(),
+        }
+    }
+
+    ///
+    /// Measures the CPU and GPU time spent in the given callback and adds it to the measurements of the scope with the given name.
+    /// Returns the result of the callback.
+    ///
+    pub fn scope<T>(
+        &mut self,
+        name: &str,
+        callback: impl FnOnce() -> ThreeDResult<T>,
+    ) -> ThreeDResult<T> {
+        self.update();
+        let index = self.scopes.iter().position(|s| s.name == name);
+        let index = index.unwrap_or_else(|| {
+            self.scopes.push(Samples {
+                name: name.to_string(),
+                cpu: VecDeque::new(),
+                gpu: VecDeque::new(),
+            });
+            self.scopes.len() - 1
+        });
+
+        let mut query = if self.supports_timer_queries {
+            Some(match self.free_queries.pop() {
+                Some(query) => query,
+                None => TimerQuery::new(&self.context)?,
+            })
+        } else {
+            None
+        };
+        if let Some(ref mut query) = query {
+            query.begin()?;
+        }
+        let (result, cpu_time) = measure_cpu_time(callback);
+        if let Some(mut query) = query {
+            query.end()?;
+            self.pending_queries.push((index, query));
+        }
+        let number_of_samples = self.number_of_samples;
+        push_sample(&mut self.scopes[index].cpu, cpu_time, number_of_samples);
+        result
+    }
+
+    ///
+    /// Returns the average CPU and GPU time of each scope in the order the scopes were first measured.
+    ///
+    pub fn report(&mut self) -> Vec<ProfilerScope> {
+        self.update();
+        self.scopes
+            .iter()
+            .map(|s| ProfilerScope {
+                name: s.name.clone(),
+                cpu_time: average(&s.cpu).unwrap_or_default(),
+                gpu_time: average(&s.gpu),
+            })
+            .collect()
+    }
+
+    ///
+    /// Removes all measurements.
+    ///
+    pub fn reset(&mut self) {
+        self.update();
+        for scope in self.scopes.iter_mut() {
+            scope.cpu.clear();
+            scope.gpu.clear();
+        }
+    }
+
+    fn update(&mut self) {
+        let mut i = 0;
+        while i < self.pending_queries.len() {
+            let (index, query) = &mut self.pending_queries[i];
+            if let Some(gpu_time) = query.result() {
+                push_sample(
+                    &mut self.scopes[*index].gpu,
+                    gpu_time,
+                    self.number_of_samples,
+                );
+            }
+            if query.is_pending() {
+                i += 1;
+            } else {
+                let (_, query) = self.pending_queries.remove(i);
+                self.free_queries.push(query);
+            }
+        }
+    }
+}
+
+fn push_sample(samples: &mut VecDeque<Duration>, sample: Duration, number_of_samples: usize) {
+    samples.push_back(sample);
+    while samples.len() > number_of_samples {
+        samples.pop_front();
+    }
+}
+
+fn average(samples: &VecDeque<Duration>) -> Option<Duration> {
+    if samples.is_empty() {
+        None
+    } else {
+        Some(samples.iter().sum::<Duration>() / samples.len() as u32)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn measure_cpu_time<T>(callback: impl FnOnce() -> T) -> (T, Duration) {
+    let start = std::time::Instant::now();
+    let result = callback();
+    (result, start.elapsed())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn measure_cpu_time<T>(callback: impl FnOnce() -> T) -> (T, Duration) {
+    let performance = web_sys::window().unwrap().performance().unwrap();
+    let start = performance.now();
+    let result = callback();
+    let milliseconds = (performance.now() - start).max(0.0);
+    (result, Duration::from_secs_f64(milliseconds / 1000.0))
+}
@@ -1,6 +1,6 @@
 use crate::core::*;
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 ///
 /// A shader program consisting of a programmable vertex shader followed by a programmable fragment shader.
@@ -15,17 +15,65 @@ pub struct Program {
     textures: RefCell<HashMap<String, u32>>,
     uniforms: HashMap<String, crate::context::UniformLocation>,
     uniform_blocks: RefCell<HashMap<String, (u32, u32)>>,
+    active_attributes: Vec<ShaderVariable>,
+    active_uniforms: Vec<ShaderVariable>,
+    active_uniform_blocks: Vec<ShaderUniformBlock>,
+    transform_feedback_varyings: Vec<ShaderVariable>,
+    vertex_shader_source: String,
+    fragment_shader_source: String,
+    resource: ResourceHandle,
 }
 
 impl Program {
     ///
     /// Creates a new shader program from the given vertex and fragment glsl shader source.
     ///
+    /// The source can include named chunks of shader code using `#include "name"` on a separate line.
+    /// The name is resolved against the chunks registered using [Context::register_shader_chunk] and the built-in library which contains
+    /// - `shared.frag`: General functionality, for example color space conversions and reading from a texture at a given uv coordinate.
+    /// - `light_shared.frag`: The BRDF functions used in the lighting calculations, shadow lookups and importance sampling. Requires the lighting model defines, for example `#define COOK` and `#define COOK_GGX`.
+    ///
+    /// Chunks can include other chunks and each chunk is only included once in a shader, also if it is included several times.
+    /// Line numbers in compilation errors refer to the line in the chunk or the source where the error occurred.
+    ///
     pub fn from_source(
         context: &Context,
         vertex_shader_source: &str,
         fragment_shader_source: &str,
     ) -> ThreeDResult<Program> {
+        Self::compile(context, vertex_shader_source, fragment_shader_source, &[])
+    }
+
+    ///
+    /// Creates a new shader program, like [Program::from_source], where the given output variables of the vertex shader are captured using transform feedback.
+    /// Use [Program::draw_arrays_with_transform_feedback] to run the vertex shader and write the values of the output variables into vertex buffers,
+    /// for example to simulate particles or to do skinning on the GPU.
+    /// The output variables must be of type `float`, `int`, `uint` or a vector of these. Array output variables are not supported.
+    /// The fragment shader is not executed when capturing the output, but must be given, for example `void main() {}`.
+    ///
+    pub fn from_source_with_transform_feedback(
+        context: &Context,
+        vertex_shader_source: &str,
+        fragment_shader_source: &str,
+        varyings: &[&str],
+    ) -> ThreeDResult<Program> {
+        Self::compile(
+            context,
+            vertex_shader_source,
+            fragment_shader_source,
+            varyings,
+        )
+    }
+
+    fn compile(
+        context: &Context,
+        vertex_shader_source: &str,
+        fragment_shader_source: &str,
+        varyings: &[&str],
+    ) -> ThreeDResult<Program> {
+        let shader_chunk = |name: &str| context.shader_chunk(name);
+        let vertex_source = ShaderSource::new("vertex", vertex_shader_source, &shader_chunk)?;
+        let fragment_source = ShaderSource::new("fragment", fragment_shader_source, &shader_chunk)?;
         unsafe {
             let vert_shader = context
                 .create_shader(crate::context::VERTEX_SHADER)
@@ -41,59 +89,103 @@ impl Program {
                         precision highp int;
                         precision highp sampler2DArray;
                         precision highp sampler3D;
+                        precision highp isampler2D;
+                        precision highp isampler2DArray;
+                        precision highp isampler3D;
+                        precision highp isamplerCube;
+                        precision highp usampler2D;
+                        precision highp usampler2DArray;
+                        precision highp usampler3D;
+                        precision highp usamplerCube;
                     #else
                         precision mediump float;
                         precision mediump int;
                         precision mediump sampler2DArray;
                         precision mediump sampler3D;
+                        precision mediump isampler2D;
+                        precision mediump isampler2DArray;
+                        precision mediump isampler3D;
+                        precision mediump isamplerCube;
+                        precision mediump usampler2D;
+                        precision mediump usampler2DArray;
+                        precision mediump usampler3D;
+                        precision mediump usamplerCube;
                     #endif\n"
             } else {
                 "#version 330 core\n"
             };
 
-            context.shader_source(vert_shader, &format!("{}{}", header, vertex_shader_source));
-            context.shader_source(
-                frag_shader,
-                &format!("{}{}", header, fragment_shader_source),
-            );
-            context.compile_shader(vert_shader);
-            context.compile_shader(frag_shader);
+            let header_lines = header.matches('\n').count();
+            let vertex_text = format!("{}{}", header, vertex_source.source);
+            let fragment_text = format!("{}{}", header, fragment_source.source);
+            let sources = [vertex_text.as_str(), fragment_text.as_str()]
+                .iter()
+                .chain(varyings.iter())
+                .copied()
+                .collect::<Vec<_>>();
 
             let id = context
                 .create_program()
                 .map_err(|e| CoreError::ProgramCreation(e))?;
-            context.attach_shader(id, vert_shader);
-            context.attach_shader(id, frag_shader);
-            context.link_program(id);
-
-            if !context.get_program_link_status(id) {
-                let log = context.get_shader_info_log(vert_shader);
-                if log.len() > 0 {
-                    Err(CoreError::ShaderCompilation("vertex".to_string(), log))?;
-                }
-                let log = context.get_shader_info_log(frag_shader);
-                if log.len() > 0 {
-                    Err(CoreError::ShaderCompilation("fragment".to_string(), log))?;
+            let binary_cache = context.program_binary_cache.borrow();
+            let name = binary_cache.program_name(id);
+            if !binary_cache.load(context, id, name, &sources)? {
+                context.shader_source(vert_shader, &vertex_text);
+                context.shader_source(frag_shader, &fragment_text);
+                context.compile_shader(vert_shader);
+                context.compile_shader(frag_shader);
+
+                context.attach_shader(id, vert_shader);
+                context.attach_shader(id, frag_shader);
+                if !varyings.is_empty() {
+                    context.transform_feedback_varyings(
+                        id,
+                        varyings,
+                        crate::context::SEPARATE_ATTRIBS,
+                    );
                 }
-                let log = context.get_program_info_log(id);
-                if log.len() > 0 {
-                    Err(CoreError::ShaderLink(log))?;
+                binary_cache.prepare(name);
+                context.link_program(id);
+
+                if !context.get_program_link_status(id) {
+                    let log = context.get_shader_info_log(vert_shader);
+                    if !log.is_empty() {
+                        Err(CoreError::ShaderCompilation(
+                            "vertex".to_string(),
+                            vertex_source.map_log(&log, header_lines),
+                        ))?;
+                    }
+                    let log = context.get_shader_info_log(frag_shader);
+                    if !log.is_empty() {
+                        Err(CoreError::ShaderCompilation(
+                            "fragment".to_string(),
+                            fragment_source.map_log(&log, header_lines),
+                        ))?;
+                    }
+                    let log = context.get_program_info_log(id);
+                    if !log.is_empty() {
+                        Err(CoreError::ShaderLink(log))?;
+                    }
+                    unreachable!();
                 }
-                unreachable!();
-            }
 
-            context.detach_shader(id, vert_shader);
-            context.detach_shader(id, frag_shader);
+                context.detach_shader(id, vert_shader);
+                context.detach_shader(id, frag_shader);
+                binary_cache.store(name, &sources);
+            }
+            drop(binary_cache);
             context.delete_shader(vert_shader);
             context.delete_shader(frag_shader);
 
             // Init vertex attributes
             let num_attribs = context.get_active_attributes(id);
             let mut attributes = HashMap::new();
+            let mut active_attributes = Vec::new();
             for i in 0..num_attribs {
-                if let Some(crate::context::ActiveAttribute { name, .. }) =
+                if let Some(crate::context::ActiveAttribute { name, atype, size }) =
                     context.get_active_attribute(id, i)
                 {
+                    active_attributes.push(ShaderVariable::new(&name, atype, size));
                     let location = context.get_attrib_location(id, &name).unwrap();
                     /*println!(
                         "Attribute location: {}, name: {}, type: {}, size: {}",
@@ -106,11 +198,19 @@ impl Program {
             // Init uniforms
             let num_uniforms = context.get_active_uniforms(id);
             let mut uniforms = HashMap::new();
+            let mut active_uniforms = Vec::new();
+            let mut num_block_uniforms = 0u32;
             for i in 0..num_uniforms {
-                if let Some(crate::context::ActiveUniform { name, .. }) =
+                if let Some(crate::context::ActiveUniform { name, utype, size }) =
                     context.get_active_uniform(id, i)
                 {
-                    if let Some(location) = context.get_uniform_location(id, &name) {
+                    // Uniforms in a uniform block do not have a location
+                    let location = context.get_uniform_location(id, &name);
+                    if location.is_none() {
+                        num_block_uniforms += 1;
+                    }
+                    if let Some(location) = location {
+                        active_uniforms.push(ShaderVariable::new(&name, utype, size));
                         let name = name.split('[').collect::<Vec<_>>()[0].to_string();
                         /*println!(
                             "Uniform location: {:?}, name: {}, type: {}, size: {}",
@@ -121,6 +221,62 @@ impl Program {
                 }
             }
 
+            // Init uniform blocks
+            // The number of active uniform blocks is not available in the low-level graphics context,
+            // so the blocks are enumerated until all uniforms without a location, ie. the uniforms in blocks, are accounted for.
+            let max_uniform_blocks =
+                context.get_parameter_i32(crate::context::MAX_COMBINED_UNIFORM_BLOCKS) as u32;
+            let mut remaining_block_uniforms = num_block_uniforms;
+            let mut active_uniform_blocks = Vec::new();
+            let mut index = 0;
+            while remaining_block_uniforms > 0 && index < max_uniform_blocks {
+                let count = context.get_active_uniform_block_parameter_i32(
+                    id,
+                    index,
+                    crate::context::UNIFORM_BLOCK_ACTIVE_UNIFORMS,
+                );
+                let mut indices = vec![0; count.max(0) as usize];
+                context.get_active_uniform_block_parameter_i32_slice(
+                    id,
+                    index,
+                    crate::context::UNIFORM_BLOCK_ACTIVE_UNIFORM_INDICES,
+                    &mut indices,
+                );
+                let indices = indices.into_iter().map(|i| i as u32).collect::<Vec<_>>();
+                let layouts = uniform_layouts(context, id, &indices);
+                let mut members = indices
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, index)| {
+                        context
+                            .get_active_uniform(id, *index)
+                            .map(|u| ShaderUniformBlockMember {
+                                variable: ShaderVariable::new(&u.name, u.utype, u.size),
+                                layout: layouts.as_ref().map(|layouts| layouts[i]),
+                            })
+                    })
+                    .collect::<Vec<_>>();
+                members.sort_by_key(|member| member.layout.map(|layout| layout.offset));
+                active_uniform_blocks.push(ShaderUniformBlock {
+                    name: context.get_active_uniform_block_name(id, index),
+                    data_size: context.get_active_uniform_block_parameter_i32(
+                        id,
+                        index,
+                        crate::context::UNIFORM_BLOCK_DATA_SIZE,
+                    ) as u32,
+                    members,
+                });
+                remaining_block_uniforms =
+                    remaining_block_uniforms.saturating_sub(count.max(0) as u32);
+                index += 1;
+            }
+
+            // Init transform feedback varyings
+            let transform_feedback_varyings = (0..varyings.len() as u32)
+                .filter_map(|i| context.get_transform_feedback_varying(id, i))
+                .map(|v| ShaderVariable::new(&v.name, v.tftype, v.size))
+                .collect();
+
             context.error_check()?;
             Ok(Program {
                 context: context.clone(),
@@ -129,10 +285,43 @@ impl Program {
                 uniforms,
                 uniform_blocks: RefCell::new(HashMap::new()),
                 textures: RefCell::new(HashMap::new()),
+                active_attributes,
+                active_uniforms,
+                active_uniform_blocks,
+                transform_feedback_varyings,
+                vertex_shader_source: vertex_shader_source.to_string(),
+                fragment_shader_source: fragment_shader_source.to_string(),
+                resource: context.track_resource(ResourceKind::Program, 0),
             })
         }
     }
 
+    ///
+    /// Compiles this program again from the vertex and fragment shader source given at construction,
+    /// which means that the current version of all included shader chunks is used (see [Context::register_shader_chunk] and [Context::reload_shaders]).
+    ///
+    /// # Errors
+    /// Returns an error, typically [CoreError::ShaderCompilation], if the compilation fails, in which case this program is left unchanged and can still be used.
+    ///
+    pub fn reload(&mut self) -> ThreeDResult<()> {
+        let varyings = self
+            .transform_feedback_varyings
+            .iter()
+            .map(|v| v.name.clone())
+            .collect::<Vec<_>>();
+        let label = self.label();
+        *self = Program::compile(
+            &self.context,
+            &self.vertex_shader_source,
+            &self.fragment_shader_source,
+            &varyings.iter().map(|v| v.as_str()).collect::<Vec<_>>(),
+        )?;
+        if let Some(label) = label {
+            self.set_label(&label);
+        }
+        Ok(())
+    }
+
     ///
     /// Send the given uniform data to this shader program and associate it with the given named variable.
     /// The glsl shader variable must be of type `uniform int` if the data is an integer, `uniform vec2` if it is of type [Vec2] etc.
@@ -141,8 +330,10 @@ impl Program {
     /// # Errors
     /// Will return an error if the uniform is not defined in the shader code or not used.
     /// In the latter case the variable is removed by the shader compiler.
+    /// Will also return an error, [CoreError::InvalidUniformType], if the type of the data does not match the type of the uniform variable.
     ///
     pub fn use_uniform<T: UniformDataType>(&self, name: &str, data: T) -> ThreeDResult<()> {
+        self.check_uniform::<T>(name, 1)?;
         let location = self.get_uniform_location(name)?;
         T::send_uniform(&self.context, location, &[data]);
         self.unuse_program();
@@ -171,12 +362,15 @@ impl Program {
     /// # Errors
     /// Will return an error if the uniform is not defined in the shader code or not used.
     /// In the latter case the variable is removed by the shader compiler.
+    /// Will also return an error if the type of the data does not match the type of the uniform variable, see [CoreError::InvalidUniformType],
+    /// or if more elements are sent than the length of the uniform array, see [CoreError::InvalidUniformArrayLength].
     ///
     pub fn use_uniform_array<T: UniformDataType>(
         &self,
         name: &str,
         data: &[T],
     ) -> ThreeDResult<()> {
+        self.check_uniform::<T>(name, data.len())?;
         let location = self.get_uniform_location(name)?;
         T::send_uniform(&self.context, location, data);
         self.unuse_program();
@@ -291,6 +485,27 @@ impl Program {
         self.use_uniform(name, data)
     }
 
+    fn check_uniform<T: UniformDataType>(&self, name: &str, length: usize) -> ThreeDResult<()> {
+        if let Some(variable) = self.active_uniforms.iter().find(|v| v.name == name) {
+            let data_type = T::uniform_data_type();
+            if !is_uniform_type_compatible(variable.data_type, data_type) {
+                Err(CoreError::InvalidUniformType(
+                    name.to_string(),
+                    variable.type_name().to_string(),
+                    glsl_type_name(data_type).to_string(),
+                ))?;
+            }
+            if length > variable.array_size as usize {
+                Err(CoreError::InvalidUniformArrayLength(
+                    name.to_string(),
+                    variable.array_size as usize,
+                    length,
+                ))?;
+            }
+        }
+        Ok(())
+    }
+
     fn get_uniform_location(&self, name: &str) -> ThreeDResult<&crate::context::UniformLocation> {
         self.use_program();
         let loc = self
@@ -302,7 +517,8 @@ impl Program {
 
     ///
     /// Use the given [Texture2D] in this shader program and associate it with the given named variable.
-    /// The glsl shader variable must be of type `uniform sampler2D` and can only be accessed in the fragment shader.
+    /// The glsl shader variable must be of type `uniform sampler2D` (or `uniform usampler2D`/`uniform isampler2D` if the texture contains unsigned/signed integer data)
+    /// and can only be accessed in the fragment shader.
     ///
     /// # Errors
     /// Will return an error if the texture is not defined in the shader code or not used.
@@ -334,7 +550,8 @@ impl Program {
 
     ///
     /// Use the given texture array in this shader program and associate it with the given named variable.
-    /// The glsl shader variable must be of type `uniform sampler2DArray` and can only be accessed in the fragment shader.
+    /// The glsl shader variable must be of type `uniform sampler2DArray` (or `uniform usampler2DArray`/`uniform isampler2DArray` if the texture contains unsigned/signed integer data)
+    /// and can only be accessed in the fragment shader.
     ///
     /// # Errors
     /// Will return an error if the texture is not defined in the shader code or not used.
@@ -366,7 +583,8 @@ impl Program {
 
     ///
     /// Use the given texture cube map in this shader program and associate it with the given named variable.
-    /// The glsl shader variable must be of type `uniform samplerCube` and can only be accessed in the fragment shader.
+    /// The glsl shader variable must be of type `uniform samplerCube` (or `uniform usamplerCube`/`uniform isamplerCube` if the texture contains unsigned/signed integer data)
+    /// and can only be accessed in the fragment shader.
     ///
     /// # Errors
     /// Will return an error if the texture is not defined in the shader code or not used.
@@ -398,7 +616,8 @@ impl Program {
 
     ///
     /// Use the given 3D texture in this shader program and associate it with the given named variable.
-    /// The glsl shader variable must be of type `uniform sampler3D` and can only be accessed in the fragment shader.
+    /// The glsl shader variable must be of type `uniform sampler3D` (or `uniform usampler3D`/`uniform isampler3D` if the texture contains unsigned/signed integer data)
+    /// and can only be accessed in the fragment shader.
     ///
     /// # Errors
     /// Will return an error if the texture is not defined in the shader code or not used.
@@ -418,10 +637,7 @@ impl Program {
         };
         let index = self.textures.borrow().get(name).unwrap().clone();
         self.use_uniform(name, index as i32)?;
-        unsafe {
-            self.context
-                .active_texture(crate::context::TEXTURE0 + index);
-        }
+        self.context.active_texture_cached(index);
         Ok(index)
     }
 
@@ -453,6 +669,8 @@ impl Program {
     /// Uses the given [VertexBuffer] data in this shader program and associates it with the given named variable.
     /// Each value in the buffer is used when rendering one vertex using the [Program::draw_arrays] or [Program::draw_elements] methods.
     /// Therefore the buffer must contain the same number of values as the number of vertices specified in those draw calls.
+    /// Integer data is converted to floating point unless the attribute is declared as `int`, `uint` or a vector of these in the shader,
+    /// so for example the output of an integer variable captured using [Program::draw_arrays_with_transform_feedback] can be used as an integer attribute.
     ///
     /// # Errors
     /// Will return an error if the attribute is not defined in the shader code or not used.
@@ -460,20 +678,52 @@ impl Program {
     ///
     pub fn use_vertex_attribute(&self, name: &str, buffer: &VertexBuffer) -> ThreeDResult<()> {
         if buffer.count() > 0 {
-            buffer.bind();
             let loc = self.location(name)?;
-            unsafe {
-                self.context.bind_vertex_array(Some(self.context.vao));
-                self.context.enable_vertex_attrib_array(loc);
-                self.context.vertex_attrib_pointer_f32(
+            buffer.bind();
+            if let Some(layout) = buffer.layout() {
+                let attribute = layout
+                    .attribute(name)
+                    .ok_or_else(|| CoreError::MissingVertexAttribute(name.to_string()))?;
+                self.bind_attribute(loc, attribute, layout.stride, 0);
+            } else {
+                self.bind_attribute_pointer(
                     loc,
-                    buffer.data_size() as i32,
+                    buffer.data_size(),
                     buffer.data_type(),
                     false,
+                    self.is_integer_attribute(name, buffer.data_type()),
+                    0,
                     0,
                     0,
                 );
-                self.context.vertex_attrib_divisor(loc, 0);
+            }
+            unsafe {
+                self.context.bind_buffer(crate::context::ARRAY_BUFFER, None);
+            }
+            self.unuse_program();
+        }
+        self.context.error_check()
+    }
+
+    ///
+    /// Uses all attributes in the [VertexLayout] of the given interleaved [VertexBuffer] (see [VertexBuffer::new_with_interleaved_data])
+    /// that are used in this shader program, attributes that are not used are ignored.
+    /// Each vertex in the buffer is used when rendering one vertex using the [Program::draw_arrays] or [Program::draw_elements] methods.
+    ///
+    /// # Errors
+    /// Will return an error if the buffer does not have a vertex layout.
+    ///
+    pub fn use_vertex_buffer(&self, buffer: &VertexBuffer) -> ThreeDResult<()> {
+        let layout = buffer.layout().ok_or(CoreError::MissingVertexLayout)?;
+        if buffer.count() > 0 {
+            buffer.bind();
+            self.use_program();
+            for attribute in layout.attributes.iter() {
+                if let Some(loc) = self.attributes.get(&attribute.name) {
+                    self.bind_attribute(*loc, attribute, layout.stride, 0);
+                }
+            }
+            unsafe {
                 self.context.bind_buffer(crate::context::ARRAY_BUFFER, None);
             }
             self.unuse_program();
@@ -485,6 +735,7 @@ impl Program {
     /// Uses the given [InstanceBuffer] data in this shader program and associates it with the given named variable.
     /// Each value in the buffer is used when rendering one instance using the [Program::draw_arrays_instanced] or [Program::draw_elements_instanced] methods.
     /// Therefore the buffer must contain the same number of values as the number of instances specified in those draw calls.
+    /// Integer data is converted to floating point unless the attribute is declared as `int`, `uint` or a vector of these in the shader.
     ///
     /// # Errors
     /// Will return an error if the attribute is not defined in the shader code or not used.
@@ -492,20 +743,19 @@ impl Program {
     ///
     pub fn use_instance_attribute(&self, name: &str, buffer: &InstanceBuffer) -> ThreeDResult<()> {
         if buffer.count() > 0 {
-            buffer.bind();
             let loc = self.location(name)?;
+            buffer.bind();
+            self.bind_attribute_pointer(
+                loc,
+                buffer.data_size(),
+                buffer.data_type(),
+                false,
+                self.is_integer_attribute(name, buffer.data_type()),
+                0,
+                0,
+                1,
+            );
             unsafe {
-                self.context.bind_vertex_array(Some(self.context.vao));
-                self.context.enable_vertex_attrib_array(loc);
-                self.context.vertex_attrib_pointer_f32(
-                    loc,
-                    buffer.data_size() as i32,
-                    buffer.data_type(),
-                    false,
-                    0,
-                    0,
-                );
-                self.context.vertex_attrib_divisor(loc, 1);
                 self.context.bind_buffer(crate::context::ARRAY_BUFFER, None);
             }
             self.unuse_program();
@@ -513,6 +763,86 @@ impl Program {
         self.context.error_check()
     }
 
+    ///
+    /// Returns whether integer data of the given type must be sent to the attribute with the given name without converting it to floating point,
+    /// which is the case if the attribute is declared as `int`, `uint` or a vector of these.
+    ///
+    fn is_integer_attribute(&self, name: &str, data_type: u32) -> bool {
+        use crate::context::*;
+        matches!(
+            data_type,
+            BYTE | UNSIGNED_BYTE | SHORT | UNSIGNED_SHORT | INT | UNSIGNED_INT
+        ) && self.active_attributes.iter().any(|a| {
+            a.name == name
+                && matches!(
+                    a.data_type,
+                    INT | INT_VEC2
+                        | INT_VEC3
+                        | INT_VEC4
+                        | UNSIGNED_INT
+                        | UNSIGNED_INT_VEC2
+                        | UNSIGNED_INT_VEC3
+                        | UNSIGNED_INT_VEC4
+                )
+        })
+    }
+
+    fn bind_attribute(
+        &self,
+        location: u32,
+        attribute: &VertexAttribute,
+        stride: u32,
+        divisor: u32,
+    ) {
+        self.bind_attribute_pointer(
+            location,
+            attribute.component_count,
+            attribute.component_type.data_type(),
+            attribute.normalized,
+            attribute.is_integer(),
+            stride,
+            attribute.offset,
+            divisor,
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn bind_attribute_pointer(
+        &self,
+        location: u32,
+        size: u32,
+        data_type: u32,
+        normalized: bool,
+        integer: bool,
+        stride: u32,
+        offset: u32,
+        divisor: u32,
+    ) {
+        unsafe {
+            self.context.use_vertex_array();
+            self.context.enable_vertex_attrib_array(location);
+            if integer {
+                self.context.vertex_attrib_pointer_i32(
+                    location,
+                    size as i32,
+                    data_type,
+                    stride as i32,
+                    offset as i32,
+                );
+            } else {
+                self.context.vertex_attrib_pointer_f32(
+                    location,
+                    size as i32,
+                    data_type,
+                    normalized,
+                    stride as i32,
+                    offset as i32,
+                );
+            }
+            self.context.vertex_attrib_divisor(location, divisor);
+        }
+    }
+
     ///
     /// Draws `count` number of triangles with the given render states and viewport using this shader program.
     /// Requires that all attributes and uniforms have been defined using the use_attribute and use_uniform methods.
@@ -534,8 +864,75 @@ impl Program {
             for location in self.attributes.values() {
                 self.context.disable_vertex_attrib_array(*location);
             }
-            self.context.bind_vertex_array(None);
         }
+        self.unbind_vertex_array();
+        self.unuse_program();
+        self.context.error_check()
+    }
+
+    ///
+    /// Runs the vertex shader for `count` vertices and writes the values of the output variables specified in [Program::from_source_with_transform_feedback]
+    /// into the given vertex buffers, one buffer for each output variable in the same order.
+    /// Each buffer is resized to contain `count` values of the type of the output variable, for example [Vec3] if the output variable is a `vec3`,
+    /// so afterwards it can be used as input to another program, for example a [Mesh](crate::renderer::Mesh).
+    /// Nothing is rendered.
+    ///
+    /// Output variables of type `int`, `uint` or a vector of these are captured as integers and can be used as integer attributes, see [Program::use_vertex_attribute].
+    ///
+    /// **Note:** An output buffer cannot at the same time be used as input to the program, use two buffers and swap between them instead.
+    ///
+    /// # Errors
+    /// Will return an error if the number of buffers does not match the number of output variables or if an output variable is not of a supported type.
+    /// Will return an error if an output buffer for `count` values is too large to be allocated.
+    ///
+    pub fn draw_arrays_with_transform_feedback(
+        &self,
+        count: u32,
+        outputs: &mut [&mut VertexBuffer],
+    ) -> ThreeDResult<()> {
+        if outputs.len() != self.transform_feedback_varyings.len() {
+            Err(CoreError::InvalidNumberOfTransformFeedbackBuffers(
+                self.transform_feedback_varyings.len(),
+                outputs.len(),
+            ))?;
+        }
+        for (index, (varying, output)) in self
+            .transform_feedback_varyings
+            .iter()
+            .zip(outputs.iter_mut())
+            .enumerate()
+        {
+            let (data_type, data_size) =
+                transform_feedback_data_type(varying).ok_or_else(|| {
+                    CoreError::InvalidTransformFeedbackVarying(
+                        varying.name.clone(),
+                        varying.type_name().to_string(),
+                    )
+                })?;
+            output.allocate(data_type, data_size, count)?;
+            output.bind_as_transform_feedback_output(index as u32);
+        }
+        self.use_program();
+        unsafe {
+            self.context.enable(crate::context::RASTERIZER_DISCARD);
+            self.context
+                .begin_transform_feedback(crate::context::POINTS);
+            self.context
+                .draw_arrays(crate::context::POINTS, 0, count as i32);
+            self.context.end_transform_feedback();
+            self.context.disable(crate::context::RASTERIZER_DISCARD);
+            for index in 0..outputs.len() {
+                self.context.bind_buffer_base(
+                    crate::context::TRANSFORM_FEEDBACK_BUFFER,
+                    index as u32,
+                    None,
+                );
+            }
+            for location in self.attributes.values() {
+                self.context.disable_vertex_attrib_array(*location);
+            }
+        }
+        self.unbind_vertex_array();
         self.unuse_program();
         self.context.error_check()
     }
@@ -566,8 +963,8 @@ impl Program {
             for location in self.attributes.values() {
                 self.context.disable_vertex_attrib_array(*location);
             }
-            self.context.bind_vertex_array(None);
         }
+        self.unbind_vertex_array();
         self.unuse_program();
         self.context.error_check()
     }
@@ -622,8 +1019,8 @@ impl Program {
             for location in self.attributes.values() {
                 self.context.disable_vertex_attrib_array(*location);
             }
-            self.context.bind_vertex_array(None);
         }
+        self.unbind_vertex_array();
         self.unuse_program();
         self.context.error_check()
     }
@@ -679,8 +1076,8 @@ impl Program {
             for location in self.attributes.values() {
                 self.context.disable_vertex_attrib_array(*location);
             }
-            self.context.bind_vertex_array(None);
         }
+        self.unbind_vertex_array();
         self.unuse_program();
         self.context.error_check()
     }
@@ -699,6 +1096,68 @@ impl Program {
         self.attributes.contains_key(name)
     }
 
+    ///
+    /// Returns all active attributes in this program, ie. the attributes that are declared and used in the vertex shader.
+    ///
+    pub fn active_attributes(&self) -> &[ShaderVariable] {
+        &self.active_attributes
+    }
+
+    ///
+    /// Returns all active uniforms in this program which are not part of a uniform block, including samplers.
+    /// An uniform is active if it is declared and used in the vertex or fragment shader, unused uniforms are removed by the shader compiler.
+    ///
+    pub fn active_uniforms(&self) -> &[ShaderVariable] {
+        &self.active_uniforms
+    }
+
+    ///
+    /// Returns the active uniforms in this program that are samplers, ie. the uniforms that should be assigned a texture,
+    /// for example using [Program::use_texture].
+    ///
+    pub fn active_samplers(&self) -> Vec<&ShaderVariable> {
+        self.active_uniforms
+            .iter()
+            .filter(|u| u.is_sampler())
+            .collect()
+    }
+
+    ///
+    /// Returns the output variables of the vertex shader which are captured using transform feedback, see [Program::from_source_with_transform_feedback].
+    ///
+    pub fn transform_feedback_varyings(&self) -> &[ShaderVariable] {
+        &self.transform_feedback_varyings
+    }
+
+    ///
+    /// Returns all active uniform blocks in this program, ie. the blocks that should be assigned a [UniformBuffer] using [Program::use_uniform_block].
+    ///
+    pub fn active_uniform_blocks(&self) -> &[ShaderUniformBlock] {
+        &self.active_uniform_blocks
+    }
+
+    ///
+    /// Sets a debug label for this program which is used to identify it in [Context::resource_stats]
+    /// and in GPU debuggers if debug output is supported, see [Context::supports_debug_output].
+    ///
+    pub fn set_label(&self, label: &str) {
+        self.resource.set_label(label);
+        self.context.label_object(
+            label,
+            crate::context::PROGRAM,
+            crate::context::CURRENT_PROGRAM,
+            || self.use_program(),
+        );
+        self.unuse_program();
+    }
+
+    ///
+    /// Returns the debug label of this program, see [Program::set_label].
+    ///
+    pub fn label(&self) -> Option<String> {
+        self.resource.label()
+    }
+
     fn location(&self, name: &str) -> ThreeDResult<u32> {
         self.use_program();
         let location = self
@@ -709,22 +1168,560 @@ impl Program {
     }
 
     fn use_program(&self) {
-        unsafe {
-            self.context.use_program(Some(self.id));
-        }
+        self.context.use_program_cached(Some(self.id));
     }
 
     fn unuse_program(&self) {
-        unsafe {
-            self.context.use_program(None);
-        }
+        self.context.use_program_cached(None);
+    }
+
+    fn unbind_vertex_array(&self) {
+        self.context.unuse_vertex_array();
     }
 }
 
 impl Drop for Program {
     fn drop(&mut self) {
+        self.context.forget_program(self.id);
         unsafe {
             self.context.delete_program(self.id);
         }
     }
 }
+
+///
+/// Describes an active variable in a [Program], for example an attribute or a uniform.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShaderVariable {
+    /// The name of the variable as it is declared in the shader. Arrays are named without brackets, ie. `values` instead of `values[0]`.
+    pub name: String,
+    /// The type of the variable given as a constant from the [context](crate::context) module, for example [FLOAT_VEC3](crate::context::FLOAT_VEC3).
+    pub data_type: u32,
+    /// The number of elements if the variable is an array, otherwise 1.
+    pub array_size: u32,
+}
+
+impl ShaderVariable {
+    fn new(name: &str, data_type: u32, array_size: i32) -> Self {
+        Self {
+            name: name.strip_suffix("[0]").unwrap_or(name).to_string(),
+            data_type,
+            array_size: array_size as u32,
+        }
+    }
+
+    ///
+    /// Returns the type of this variable as it is written in GLSL, for example `vec3` or `sampler2D`.
+    ///
+    pub fn type_name(&self) -> &'static str {
+        glsl_type_name(self.data_type)
+    }
+
+    ///
+    /// Returns whether or not this variable is a sampler, ie. whether it should be assigned a texture.
+    ///
+    pub fn is_sampler(&self) -> bool {
+        self.type_name().contains("sampler")
+    }
+}
+
+///
+/// Describes an active uniform block in a [Program].
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShaderUniformBlock {
+    /// The name of the uniform block as it is declared in the shader.
+    pub name: String,
+    /// The minimum size in bytes of the buffer that is assigned to the uniform block, including any padding required by the layout of the block.
+    pub data_size: u32,
+    /// The active variables in the uniform block, sorted by their offset in the buffer if the layout is available.
+    pub members: Vec<ShaderUniformBlockMember>,
+}
+
+///
+/// Describes an active variable in a [ShaderUniformBlock].
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShaderUniformBlockMember {
+    /// The name, type and array size of the variable.
+    pub variable: ShaderVariable,
+    /// Where the variable is stored in the buffer that is assigned to the uniform block.
+    /// This is `None` if the graphics function which returns the layout is not loaded (see [Context::load_additional_functions]), which is always the case on web.
+    pub layout: Option<ShaderUniformLayout>,
+}
+
+///
+/// Describes where a variable in a uniform block is stored in the buffer that is assigned to the uniform block, see [ShaderUniformBlockMember].
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShaderUniformLayout {
+    /// The offset in bytes from the start of the buffer to the variable or to the first element if the variable is an array.
+    pub offset: u32,
+    /// The number of bytes between the start of two consecutive elements if the variable is an array, otherwise 0.
+    pub array_stride: u32,
+    /// The number of bytes between the start of two consecutive columns (or rows if the matrix is row major) if the variable is a matrix, otherwise 0.
+    pub matrix_stride: u32,
+}
+
+pub(super) type GetActiveUniformsIv =
+    unsafe extern "system" fn(u32, i32, *const u32, u32, *mut i32);
+
+///
+/// Returns the layout of the uniforms with the given indices in the given linked program, or `None` if the graphics function which returns the layout is not loaded.
+///
+fn uniform_layouts(
+    context: &Context,
+    program: crate::context::Program,
+    indices: &[u32],
+) -> Option<Vec<ShaderUniformLayout>> {
+    let get_active_uniforms_iv = context.get_active_uniforms_iv.get()?;
+    // The function needs the name of the program in the graphics driver, which is only available through the program in use.
+    context.use_program_cached(Some(program));
+    let name = unsafe { context.get_parameter_i32(crate::context::CURRENT_PROGRAM) as u32 };
+    context.use_program_cached(None);
+    let parameter = |parameter| {
+        let mut values = vec![0; indices.len()];
+        unsafe {
+            get_active_uniforms_iv(
+                name,
+                indices.len() as i32,
+                indices.as_ptr(),
+                parameter,
+                values.as_mut_ptr(),
+            );
+        }
+        values
+    };
+    let offsets = parameter(crate::context::UNIFORM_OFFSET);
+    let array_strides = parameter(crate::context::UNIFORM_ARRAY_STRIDE);
+    let matrix_strides = parameter(crate::context::UNIFORM_MATRIX_STRIDE);
+    Some(
+        (0..indices.len())
+            .map(|i| ShaderUniformLayout {
+                offset: offsets[i] as u32,
+                array_stride: array_strides[i] as u32,
+                matrix_stride: matrix_strides[i] as u32,
+            })
+            .collect(),
+    )
+}
+
+fn glsl_type_name(data_type: u32) -> &'static str {
+    match data_type {
+        crate::context::FLOAT => "float",
+        crate::context::FLOAT_VEC2 => "vec2",
+        crate::context::FLOAT_VEC3 => "vec3",
+        crate::context::FLOAT_VEC4 => "vec4",
+        crate::context::INT => "int",
+        crate::context::INT_VEC2 => "ivec2",
+        crate::context::INT_VEC3 => "ivec3",
+        crate::context::INT_VEC4 => "ivec4",
+        crate::context::UNSIGNED_INT => "uint",
+        crate::context::UNSIGNED_INT_VEC2 => "uvec2",
+        crate::context::UNSIGNED_INT_VEC3 => "uvec3",
+        crate::context::UNSIGNED_INT_VEC4 => "uvec4",
+        crate::context::BOOL => "bool",
+        crate::context::BOOL_VEC2 => "bvec2",
+        crate::context::BOOL_VEC3 => "bvec3",
+        crate::context::BOOL_VEC4 => "bvec4",
+        crate::context::FLOAT_MAT2 => "mat2",
+        crate::context::FLOAT_MAT3 => "mat3",
+        crate::context::FLOAT_MAT4 => "mat4",
+        crate::context::FLOAT_MAT2x3 => "mat2x3",
+        crate::context::FLOAT_MAT2x4 => "mat2x4",
+        crate::context::FLOAT_MAT3x2 => "mat3x2",
+        crate::context::FLOAT_MAT3x4 => "mat3x4",
+        crate::context::FLOAT_MAT4x2 => "mat4x2",
+        crate::context::FLOAT_MAT4x3 => "mat4x3",
+        crate::context::SAMPLER_2D => "sampler2D",
+        crate::context::SAMPLER_3D => "sampler3D",
+        crate::context::SAMPLER_CUBE => "samplerCube",
+        crate::context::SAMPLER_2D_ARRAY => "sampler2DArray",
+        crate::context::SAMPLER_2D_SHADOW => "sampler2DShadow",
+        crate::context::SAMPLER_2D_ARRAY_SHADOW => "sampler2DArrayShadow",
+        crate::context::SAMPLER_CUBE_SHADOW => "samplerCubeShadow",
+        crate::context::INT_SAMPLER_2D => "isampler2D",
+        crate::context::INT_SAMPLER_3D => "isampler3D",
+        crate::context::INT_SAMPLER_CUBE => "isamplerCube",
+        crate::context::INT_SAMPLER_2D_ARRAY => "isampler2DArray",
+        crate::context::UNSIGNED_INT_SAMPLER_2D => "usampler2D",
+        crate::context::UNSIGNED_INT_SAMPLER_3D => "usampler3D",
+        crate::context::UNSIGNED_INT_SAMPLER_CUBE => "usamplerCube",
+        crate::context::UNSIGNED_INT_SAMPLER_2D_ARRAY => "usampler2DArray",
+        _ => "unknown",
+    }
+}
+
+///
+/// Returns the data type and number of components of the values written to a buffer when capturing the given variable using transform feedback.
+///
+fn transform_feedback_data_type(varying: &ShaderVariable) -> Option<(u32, u32)> {
+    use crate::context::*;
+    if varying.array_size != 1 {
+        return None;
+    }
+    match varying.data_type {
+        FLOAT => Some((FLOAT, 1)),
+        FLOAT_VEC2 => Some((FLOAT, 2)),
+        FLOAT_VEC3 => Some((FLOAT, 3)),
+        FLOAT_VEC4 => Some((FLOAT, 4)),
+        INT => Some((INT, 1)),
+        INT_VEC2 => Some((INT, 2)),
+        INT_VEC3 => Some((INT, 3)),
+        INT_VEC4 => Some((INT, 4)),
+        UNSIGNED_INT => Some((UNSIGNED_INT, 1)),
+        UNSIGNED_INT_VEC2 => Some((UNSIGNED_INT, 2)),
+        UNSIGNED_INT_VEC3 => Some((UNSIGNED_INT, 3)),
+        UNSIGNED_INT_VEC4 => Some((UNSIGNED_INT, 4)),
+        _ => None,
+    }
+}
+
+///
+/// Returns whether or not data of the given type can be sent to a uniform variable declared with the given type.
+/// Boolean variables can be set using any scalar type with the same number of components and samplers are set using an integer, the texture unit.
+///
+fn is_uniform_type_compatible(declared: u32, sent: u32) -> bool {
+    use crate::context::*;
+    if declared == sent || glsl_type_name(declared) == "unknown" {
+        return true;
+    }
+    let components = |data_type: u32| match data_type {
+        BOOL | INT | UNSIGNED_INT | FLOAT => Some(1),
+        BOOL_VEC2 | INT_VEC2 | UNSIGNED_INT_VEC2 | FLOAT_VEC2 => Some(2),
+        BOOL_VEC3 | INT_VEC3 | UNSIGNED_INT_VEC3 | FLOAT_VEC3 => Some(3),
+        BOOL_VEC4 | INT_VEC4 | UNSIGNED_INT_VEC4 | FLOAT_VEC4 => Some(4),
+        _ => None,
+    };
+    match declared {
+        BOOL | BOOL_VEC2 | BOOL_VEC3 | BOOL_VEC4 => {
+            components(declared).is_some() && components(declared) == components(sent)
+        }
+        _ => glsl_type_name(declared).contains("sampler") && sent == INT,
+    }
+}
+
+///
+/// Returns the source of the chunk with the given name from the built-in shader library, see [Program::from_source].
+///
+pub(in crate::core) fn builtin_shader_chunk(name: &str) -> Option<&'static str> {
+    match name {
+        "shared.frag" => Some(include_str!("shared.frag")),
+        "light_shared.frag" => Some(include_str!("shared/light_shared.frag")),
+        _ => None,
+    }
+}
+
+///
+/// Shader source where all `#include` directives are resolved, together with the chunk name and line number each line originates from.
+///
+struct ShaderSource {
+    source: String,
+    origins: Vec<(String, usize)>,
+}
+
+impl ShaderSource {
+    ///
+    /// Resolves the `#include` directives in the given source using the given function which returns the source of the chunk with the given name.
+    ///
+    fn new(
+        name: &str,
+        source: &str,
+        shader_chunk: &dyn Fn(&str) -> Option<String>,
+    ) -> ThreeDResult<Self> {
+        let mut shader_source = Self {
+            source: String::new(),
+            origins: Vec::new(),
+        };
+        shader_source.append(name, source, shader_chunk, &mut HashSet::new())?;
+        Ok(shader_source)
+    }
+
+    fn append(
+        &mut self,
+        name: &str,
+        source: &str,
+        shader_chunk: &dyn Fn(&str) -> Option<String>,
+        included: &mut HashSet<String>,
+    ) -> ThreeDResult<()> {
+        for (i, line) in source.lines().enumerate() {
+            if let Some(chunk_name) = include_name(line) {
+                if included.insert(chunk_name.to_string()) {
+                    let chunk = shader_chunk(chunk_name).ok_or_else(|| {
+                        CoreError::MissingShaderChunk(chunk_name.to_string(), name.to_string())
+                    })?;
+                    self.append(chunk_name, &chunk, shader_chunk, included)?;
+                }
+            } else {
+                self.source.push_str(line);
+                self.source.push('\n');
+                self.origins.push((name.to_string(), i + 1));
+            }
+        }
+        Ok(())
+    }
+
+    ///
+    /// Replaces the line numbers in the given info log, which refer to the line in the source given to the driver,
+    /// with the name of the chunk and the line number in that chunk.
+    /// The drivers report the position as either `0:<line>` or `0(<line>)` where 0 is the index of the source string.
+    ///
+    fn map_log(&self, log: &str, header_lines: usize) -> String {
+        log.lines()
+            .map(|line| self.map_log_line(line, header_lines))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn map_log_line(&self, line: &str, header_lines: usize) -> String {
+        for (i, _) in line.match_indices('0') {
+            if i > 0 && line.as_bytes()[i - 1].is_ascii_digit() {
+                continue;
+            }
+            let rest = &line[i + 1..];
+            let (open, close) = if rest.starts_with(':') {
+                (":", "")
+            } else if rest.starts_with('(') {
+                ("(", ")")
+            } else {
+                continue;
+            };
+            let digits = rest[1..].chars().take_while(|c| c.is_ascii_digit()).count();
+            if digits == 0 || !rest[1 + digits..].starts_with(close) {
+                continue;
+            }
+            let line_number: usize = rest[1..1 + digits].parse().unwrap();
+            let (name, origin_line) = line_number
+                .checked_sub(header_lines + 1)
+                .and_then(|index| self.origins.get(index))
+                .map(|(name, origin_line)| (name.as_str(), *origin_line))
+                .unwrap_or(("header", line_number));
+            return format!(
+                "{}{}{}{}{}{}",
+                &line[..i],
+                name,
+                open,
+                origin_line,
+                close,
+                &rest[1 + digits + close.len()..]
+            );
+        }
+        line.to_string()
+    }
+}
+
+fn include_name(line: &str) -> Option<&str> {
+    line.trim()
+        .strip_prefix("#include")?
+        .trim()
+        .strip_prefix('"')?
+        .strip_suffix('"')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "window")]
+    const VERTEX_SHADER: &str = "void main() { gl_Position = vec4(0.0); }";
+    #[cfg(feature = "window")]
+    const FRAGMENT_SHADER: &str =
+        "uniform float value;\nlayout (location = 0) out vec4 color;\nvoid main() { color = vec4(value); }";
+
+    #[cfg(feature = "window")]
+    fn context() -> Option<Context> {
+        // Skip the tests that need a graphics driver when none is available.
+        std::panic::catch_unwind(|| Context::new().ok())
+            .ok()
+            .flatten()
+    }
+
+    #[cfg(feature = "window")]
+    fn current_program(context: &Context) -> i32 {
+        unsafe { context.get_parameter_i32(crate::context::CURRENT_PROGRAM) }
+    }
+
+    #[cfg(feature = "window")]
+    #[test]
+    fn raw_and_cached_program_binds() {
+        let context = match context() {
+            Some(context) => context,
+            None => return,
+        };
+        assert!(context.is_state_cache_enabled());
+        let program = Program::from_source(&context, VERTEX_SHADER, FRAGMENT_SHADER).unwrap();
+        let other = Program::from_source(&context, VERTEX_SHADER, FRAGMENT_SHADER).unwrap();
+        for value in [1.0f32, 2.0, 3.0] {
+            unsafe {
+                context.use_program(Some(other.id));
+            }
+            program.use_uniform("value", value).unwrap();
+            assert_eq!(current_program(&context), 0);
+            let location = program.get_uniform_location("value").unwrap();
+            let mut result = [0.0];
+            unsafe {
+                context.get_uniform_f32(program.id, &location, &mut result);
+            }
+            assert_eq!(result[0], value);
+
+            program.use_uniform("value", value).unwrap();
+            unsafe {
+                context.use_program(Some(other.id));
+            }
+            other.use_uniform("value", value).unwrap();
+            assert_eq!(current_program(&context), 0);
+        }
+    }
+
+    #[test]
+    fn shader_variable_name_without_brackets() {
+        let variable = ShaderVariable::new("values[0]", crate::context::FLOAT_VEC3, 4);
+        assert_eq!(variable.name, "values");
+        assert_eq!(variable.array_size, 4);
+        assert_eq!(
+            ShaderVariable::new("lights[0].color", crate::context::FLOAT_VEC3, 1).name,
+            "lights[0].color"
+        );
+    }
+
+    #[test]
+    fn uniform_type_compatibility() {
+        use crate::context::*;
+        assert!(is_uniform_type_compatible(
+            FLOAT_VEC3,
+            Vec3::uniform_data_type()
+        ));
+        assert!(is_uniform_type_compatible(
+            FLOAT_MAT4,
+            Mat4::uniform_data_type()
+        ));
+        assert!(is_uniform_type_compatible(INT, i32::uniform_data_type()));
+        assert!(is_uniform_type_compatible(
+            UNSIGNED_INT_VEC2,
+            Vector2::<u32>::uniform_data_type()
+        ));
+        assert!(!is_uniform_type_compatible(
+            FLOAT_VEC3,
+            Vec4::uniform_data_type()
+        ));
+        assert!(!is_uniform_type_compatible(FLOAT, i32::uniform_data_type()));
+        assert!(!is_uniform_type_compatible(INT, u32::uniform_data_type()));
+
+        // Booleans can be set using any scalar type with the same number of components.
+        assert!(is_uniform_type_compatible(BOOL, i32::uniform_data_type()));
+        assert!(is_uniform_type_compatible(
+            BOOL_VEC3,
+            Vec3::uniform_data_type()
+        ));
+        assert!(!is_uniform_type_compatible(
+            BOOL_VEC2,
+            Vec3::uniform_data_type()
+        ));
+
+        // Samplers are set using the texture unit.
+        assert!(is_uniform_type_compatible(SAMPLER_2D, INT));
+        assert!(!is_uniform_type_compatible(SAMPLER_2D, FLOAT));
+
+        // Unknown types are not validated.
+        assert!(is_uniform_type_compatible(0, FLOAT));
+    }
+
+    #[cfg(feature = "window")]
+    #[test]
+    fn uniform_block_layout() {
+        let context = match context() {
+            Some(context) => context,
+            None => return,
+        };
+        let program = Program::from_source(
+            &context,
+            VERTEX_SHADER,
+            "layout (std140) uniform Block { vec3 a; float b; vec4 c[2]; mat4 d; };
+            layout (location = 0) out vec4 color;
+            void main() { color = vec4(a, b) + c[0] + c[1] + d[0]; }",
+        )
+        .unwrap();
+        let block = &program.active_uniform_blocks()[0];
+        assert_eq!(block.name, "Block");
+        assert_eq!(block.data_size, 112);
+        let layouts = block
+            .members
+            .iter()
+            .map(|member| (member.variable.name.as_str(), member.layout.unwrap()))
+            .collect::<Vec<_>>();
+        let layout = |offset, array_stride, matrix_stride| ShaderUniformLayout {
+            offset,
+            array_stride,
+            matrix_stride,
+        };
+        assert_eq!(
+            layouts,
+            vec![
+                ("a", layout(0, 0, 0)),
+                ("b", layout(12, 0, 0)),
+                ("c", layout(16, 16, 0)),
+                ("d", layout(48, 0, 16)),
+            ]
+        );
+    }
+
+    fn chunks(name: &str) -> Option<String> {
+        match name {
+            "a" => Some("float a;\n#include \"b\"\nfloat a2;".to_string()),
+            "b" => Some("float b;".to_string()),
+            "c" => Some("#include \"missing\"".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn resolve_nested_includes() {
+        let source = ShaderSource::new(
+            "main",
+            "#version 300 es\n#include \"a\"\n  #include \"b\"\nvoid main() {}",
+            &chunks,
+        )
+        .unwrap();
+        assert_eq!(
+            source.source,
+            "#version 300 es\nfloat a;\nfloat b;\nfloat a2;\nvoid main() {}\n"
+        );
+        assert_eq!(
+            source.origins,
+            vec![
+                ("main".to_string(), 1),
+                ("a".to_string(), 1),
+                ("b".to_string(), 1),
+                ("a".to_string(), 3),
+                ("main".to_string(), 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_include() {
+        let error = ShaderSource::new("main", "#include \"c\"", &chunks)
+            .err()
+            .expect("expected a missing shader chunk error");
+        match error.downcast_ref::<CoreError>() {
+            Some(CoreError::MissingShaderChunk(chunk, name)) => {
+                assert_eq!(chunk, "missing");
+                assert_eq!(name, "c");
+            }
+            _ => panic!("expected a missing shader chunk error, got {}", error),
+        }
+    }
+
+    #[test]
+    fn map_driver_log_lines() {
+        let source = ShaderSource::new("main", "#include \"a\"\nvoid main() {}", &chunks).unwrap();
+        assert_eq!(
+            source.map_log(
+                "ERROR: 0:5: 'x' : undeclared identifier\n0(3) : error C0000: syntax error\nERROR: 0:1: in header\n2 compilation errors",
+                1
+            ),
+            "ERROR: main:2: 'x' : undeclared identifier\nb(1) : error C0000: syntax error\nERROR: header:1: in header\n2 compilation errors"
+        );
+    }
+}
@@ -0,0 +1,285 @@
+use crate::core::*;
+use std::ffi::c_void;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+
+type GetProgramIv = unsafe extern "system" fn(u32, u32, *mut i32);
+type ProgramParameterI = unsafe extern "system" fn(u32, u32, i32);
+type GetProgramBinary = unsafe extern "system" fn(u32, i32, *mut i32, *mut u32, *mut c_void);
+type ProgramBinary = unsafe extern "system" fn(u32, u32, *const c_void, i32);
+
+// The number of error flags a driver can have set at the same time is implementation dependent, but small.
+const MAX_DISCARDED_ERRORS: usize = 8;
+
+///
+/// The graphics functions used for program binaries which are not available in the low-level graphics context, see [Context::load_additional_functions].
+///
+#[derive(Clone, Copy)]
+struct ProgramBinaryFunctions {
+    get_program_iv: GetProgramIv,
+    program_parameter_i: ProgramParameterI,
+    get_program_binary: GetProgramBinary,
+    program_binary: ProgramBinary,
+}
+
+impl ProgramBinaryFunctions {
+    unsafe fn load(mut loader: impl FnMut(&str) -> *const c_void) -> Option<Self> {
+        let get_program_iv = loader("glGetProgramiv");
+        let program_parameter_i = loader("glProgramParameteri");
+        let get_program_binary = loader("glGetProgramBinary");
+        let program_binary = loader("glProgramBinary");
+        if get_program_iv.is_null()
+            || program_parameter_i.is_null()
+            || get_program_binary.is_null()
+            || program_binary.is_null()
+        {
+            return None;
+        }
+        Some(Self {
+            get_program_iv: std::mem::transmute::<*const c_void, GetProgramIv>(get_program_iv),
+            program_parameter_i: std::mem::transmute::<*const c_void, ProgramParameterI>(
+                program_parameter_i,
+            ),
+            get_program_binary: std::mem::transmute::<*const c_void, GetProgramBinary>(
+                get_program_binary,
+            ),
+            program_binary: std::mem::transmute::<*const c_void, ProgramBinary>(program_binary),
+        })
+    }
+}
+
+#[derive(Default)]
+pub(super) struct ProgramBinaryCache {
+    functions: Option<ProgramBinaryFunctions>,
+    directory: Option<PathBuf>,
+    driver: String,
+}
+
+impl ProgramBinaryCache {
+    pub(super) unsafe fn load_functions(&mut self, loader: impl FnMut(&str) -> *const c_void) {
+        self.functions = ProgramBinaryFunctions::load(loader);
+    }
+
+    pub(super) fn is_supported(&self) -> bool {
+        self.functions.is_some()
+    }
+
+    pub(super) fn set_directory(
+        &mut self,
+        context: &Context,
+        directory: PathBuf,
+        driver: String,
+    ) -> ThreeDResult<()> {
+        if !Self::verify_program_names(context)? {
+            Err(CoreError::ProgramBinaryNotSupported)?;
+        }
+        self.directory = Some(directory);
+        self.driver = driver;
+        Ok(())
+    }
+
+    ///
+    /// Returns the name of the given program in the graphics driver if the cache is in use, otherwise `None`.
+    ///
+    pub(super) fn program_name(&self, program: crate::context::Program) -> Option<u32> {
+        if self.directory.is_some() && self.functions.is_some() {
+            program_name(program)
+        } else {
+            None
+        }
+    }
+
+    ///
+    /// Checks that the name returned by [program_name] is the name used by the graphics driver, by linking a minimal program and comparing with the name of the program in use.
+    /// This is only done once when the cache is set up, since the name must be known before a program is loaded from a binary, and therefore before it is linked.
+    ///
+    fn verify_program_names(context: &Context) -> ThreeDResult<bool> {
+        let version = if context.version().is_embedded {
+            "#version 300 es"
+        } else {
+            "#version 330 core"
+        };
+        unsafe {
+            let program = context
+                .create_program()
+                .map_err(CoreError::ProgramCreation)?;
+            let vertex_shader = context
+                .create_shader(crate::context::VERTEX_SHADER)
+                .map_err(CoreError::ShaderCreation)?;
+            let fragment_shader = context
+                .create_shader(crate::context::FRAGMENT_SHADER)
+                .map_err(CoreError::ShaderCreation)?;
+            context.shader_source(
+                vertex_shader,
+                &format!("{}\nvoid main() {{ gl_Position = vec4(0.0); }}", version),
+            );
+            context.shader_source(
+                fragment_shader,
+                &format!(
+                    "{}\nprecision mediump float;\nout vec4 color;\nvoid main() {{ color = vec4(0.0); }}",
+                    version
+                ),
+            );
+            context.compile_shader(vertex_shader);
+            context.compile_shader(fragment_shader);
+            context.attach_shader(program, vertex_shader);
+            context.attach_shader(program, fragment_shader);
+            context.link_program(program);
+            let is_verified = context.get_program_link_status(program) && {
+                context.use_program_cached(Some(program));
+                let name = context.get_parameter_i32(crate::context::CURRENT_PROGRAM) as u32;
+                context.use_program_cached(None);
+                program_name(program) == Some(name)
+            };
+            context.detach_shader(program, vertex_shader);
+            context.detach_shader(program, fragment_shader);
+            context.delete_shader(vertex_shader);
+            context.delete_shader(fragment_shader);
+            context.delete_program(program);
+            context.error_check()?;
+            Ok(is_verified)
+        }
+    }
+
+    ///
+    /// Tells the driver that the binary of the program will be retrieved. Must be called before the program is linked.
+    ///
+    pub(super) fn prepare(&self, name: Option<u32>) {
+        if let (Some(functions), Some(name)) = (self.functions, name) {
+            unsafe {
+                (functions.program_parameter_i)(
+                    name,
+                    crate::context::PROGRAM_BINARY_RETRIEVABLE_HINT,
+                    crate::context::TRUE as i32,
+                );
+            }
+        }
+    }
+
+    ///
+    /// Loads the binary stored for the given sources into the program and returns whether or not the program is linked successfully.
+    ///
+    pub(super) fn load(
+        &self,
+        context: &Context,
+        program: crate::context::Program,
+        name: Option<u32>,
+        sources: &[&str],
+    ) -> ThreeDResult<bool> {
+        let (functions, path, name) = match (self.functions, self.path(sources), name) {
+            (Some(functions), Some(path), Some(name)) => (functions, path, name),
+            _ => return Ok(false),
+        };
+        let bytes = match std::fs::read(path) {
+            Ok(bytes) if bytes.len() > 4 => bytes,
+            _ => return Ok(false),
+        };
+        let format = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        let binary = &bytes[4..];
+        // Make sure that the errors discarded below are caused by loading the binary.
+        context.error_check()?;
+        unsafe {
+            (functions.program_binary)(
+                name,
+                format,
+                binary.as_ptr() as *const c_void,
+                binary.len() as i32,
+            );
+            // The driver rejects the binary if it has been updated since the binary was stored,
+            // which is reported as a failed link and possibly an error.
+            for _ in 0..MAX_DISCARDED_ERRORS {
+                if context.get_error() == crate::context::NO_ERROR {
+                    break;
+                }
+            }
+            Ok(context.get_program_link_status(program))
+        }
+    }
+
+    ///
+    /// Stores the binary of the linked program for the given sources. Failures are ignored, since the program can always be compiled instead.
+    ///
+    pub(super) fn store(&self, name: Option<u32>, sources: &[&str]) {
+        let (functions, path, name) = match (self.functions, self.path(sources), name) {
+            (Some(functions), Some(path), Some(name)) => (functions, path, name),
+            _ => return,
+        };
+        let mut length = 0;
+        unsafe {
+            (functions.get_program_iv)(name, crate::context::PROGRAM_BINARY_LENGTH, &mut length);
+        }
+        if length <= 0 {
+            return;
+        }
+        let mut bytes = vec![0u8; 4 + length as usize];
+        let mut written = 0;
+        let mut format = 0;
+        unsafe {
+            (functions.get_program_binary)(
+                name,
+                length,
+                &mut written,
+                &mut format,
+                bytes[4..].as_mut_ptr() as *mut c_void,
+            );
+        }
+        if written <= 0 {
+            return;
+        }
+        bytes.truncate(4 + written as usize);
+        bytes[..4].copy_from_slice(&format.to_le_bytes());
+        // Write to a temporary file first, so another process never reads a partially written binary.
+        let temporary_path = path.with_extension("tmp");
+        if std::fs::write(&temporary_path, &bytes).is_ok() {
+            let _ = std::fs::rename(&temporary_path, &path);
+        }
+    }
+
+    fn path(&self, sources: &[&str]) -> Option<PathBuf> {
+        let directory = self.directory.as_ref()?;
+        // FNV-1a, since the hash must be the same across runs and versions of the standard library.
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for part in std::iter::once(self.driver.as_str()).chain(sources.iter().copied()) {
+            for byte in part.bytes().chain(std::iter::once(0)) {
+                hash ^= byte as u64;
+                hash = hash.wrapping_mul(0x100000001b3);
+            }
+        }
+        Some(directory.join(format!("{:016x}.bin", hash)))
+    }
+}
+
+///
+/// Returns the name of the given program in the graphics driver.
+/// The low-level graphics context does not give access to the name, but it is the only data written by the [Hash] implementation of a program.
+///
+fn program_name(program: crate::context::Program) -> Option<u32> {
+    let mut hasher = NameHasher::default();
+    program.hash(&mut hasher);
+    match hasher.written[..] {
+        [name] => name,
+        _ => None,
+    }
+}
+
+///
+/// A [Hasher] which records the values written to it, see [program_name].
+///
+#[derive(Default)]
+struct NameHasher {
+    written: Vec<Option<u32>>,
+}
+
+impl Hasher for NameHasher {
+    fn finish(&self) -> u64 {
+        0
+    }
+
+    fn write(&mut self, _bytes: &[u8]) {
+        self.written.push(None);
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.written.push(Some(i));
+    }
+}
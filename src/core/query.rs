@@ -0,0 +1,260 @@
+use crate::core::*;
+use std::time::Duration;
+
+// From the EXT_disjoint_timer_query extension which is not part of the context module.
+const GPU_DISJOINT_EXT: u32 = 0x8FBB;
+
+///
+/// Measures the time it takes for the GPU to execute the draw calls issued between [TimerQuery::begin] and [TimerQuery::end],
+/// see also [TimerQuery::measure] and [Profiler].
+/// The GPU executes the draw calls asynchronously, so the result is typically not available until one or more frames later.
+/// Only one timer query can be active at a time.
+///
+pub struct TimerQuery {
+    context: Context,
+    id: crate::context::Query,
+    is_active: bool,
+    is_pending: bool,
+}
+
+impl TimerQuery {
+    ///
+    /// Creates a new timer query.
+    ///
+    /// # Errors
+    /// Returns an error if timer queries are not supported, see [Context::supports_timer_queries].
+    ///
+    pub fn new(context: &Context) -> ThreeDResult<Self> {
+        if !context.supports_timer_queries() {
+            Err(CoreError::TimerQueryNotSupported)?;
+        }
+        let id = unsafe { context.create_query().map_err(CoreError::QueryCreation)? };
+        Ok(Self {
+            context: context.clone(),
+            id,
+            is_active: false,
+            is_pending: false,
+        })
+    }
+
+    ///
+    /// Starts measuring the time. The result of a previous measurement is discarded if it has not been read yet.
+    ///
+    pub fn begin(&mut self) -> ThreeDResult<()> {
+        if !self.is_active {
+            if self.context.version().is_embedded {
+                // Reading the disjoint state resets it
+                unsafe { self.context.get_parameter_i32(GPU_DISJOINT_EXT) };
+            }
+            unsafe {
+                self.context
+                    .begin_query(crate::context::TIME_ELAPSED, self.id);
+            }
+            self.is_active = true;
+            self.is_pending = false;
+        }
+        self.context.error_check()
+    }
+
+    ///
+    /// Stops measuring the time. The result can be read using [TimerQuery::result] when it is available.
+    ///
+    pub fn end(&mut self) -> ThreeDResult<()> {
+        if self.is_active {
+            unsafe {
+                self.context.end_query(crate::context::TIME_ELAPSED);
+            }
+            self.is_active = false;
+            self.is_pending = true;
+        }
+        self.context.error_check()
+    }
+
+    ///
+    /// Measures the time it takes for the GPU to execute the draw calls issued in the callback,
+    /// which is the same as calling [TimerQuery::begin] before and [TimerQuery::end] after the callback.
+    ///
+    pub fn measure(&mut self, callback: impl FnOnce() -> ThreeDResult<()>) -> ThreeDResult<()> {
+        self.begin()?;
+        let result = callback();
+        self.end()?;
+        result
+    }
+
+    ///
+    /// Returns the measured GPU time if the result is available, otherwise `None`.
+    /// This method does not wait for the GPU to finish, so it should be called again later, for example the next frame, if the result is not yet available.
+    /// The result is also `None` if the measurement was invalidated, for example because the GPU changed frequency, or if nothing has been measured.
+    ///
+    /// **Note:** The GPU time is read as a 32 bit number of nanoseconds, so it is only possible to measure up to approximately 4.29 seconds.
+    /// If the measured time is longer than that, the result is also `None`.
+    ///
+    pub fn result(&mut self) -> Option<Duration> {
+        if !self.is_pending {
+            return None;
+        }
+        unsafe {
+            if self
+                .context
+                .get_query_parameter_u32(self.id, crate::context::QUERY_RESULT_AVAILABLE)
+                == 0
+            {
+                return None;
+            }
+            self.is_pending = false;
+            if self.context.version().is_embedded
+                && self.context.get_parameter_i32(GPU_DISJOINT_EXT) != 0
+            {
+                return None;
+            }
+            let nanoseconds = self
+                .context
+                .get_query_parameter_u32(self.id, crate::context::QUERY_RESULT);
+            // The driver saturates the result if it does not fit into 32 bits.
+            if nanoseconds == u32::MAX {
+                return None;
+            }
+            Some(Duration::from_nanos(nanoseconds as u64))
+        }
+    }
+
+    ///
+    /// Returns whether or not the query has ended but the result has not yet been read using [TimerQuery::result].
+    ///
+    pub fn is_pending(&self) -> bool {
+        self.is_pending
+    }
+}
+
+impl Drop for TimerQuery {
+    fn drop(&mut self) {
+        unsafe {
+            self.context.delete_query(self.id);
+        }
+    }
+}
+
+///
+/// Determines whether any samples pass the depth and stencil tests when executing the draw calls issued between [OcclusionQuery::begin] and [OcclusionQuery::end],
+/// ie. whether anything drawn is visible. This is typically used for occlusion culling by drawing the bounding box of an object
+/// with color and depth writes disabled (see [WriteMask::NONE]) and only drawing the object if the bounding box is visible.
+/// The GPU executes the draw calls asynchronously, so the result is typically not available until one or more frames later.
+/// Only one occlusion query can be active at a time.
+///
+pub struct OcclusionQuery {
+    context: Context,
+    id: crate::context::Query,
+    target: u32,
+    is_active: bool,
+    is_pending: bool,
+}
+
+impl OcclusionQuery {
+    ///
+    /// Creates a new occlusion query. If `conservative` is true, the query is allowed to use a less precise test which is faster,
+    /// but which might report that samples passed even though none did.
+    ///
+    /// **Note:** The conservative test requires OpenGL 4.3 or the `ARB_ES3_compatibility` extension on desktop,
+    /// so the precise test is used instead if neither is available. It is always available on web and OpenGL ES.
+    ///
+    pub fn new(context: &Context, conservative: bool) -> ThreeDResult<Self> {
+        let id = unsafe { context.create_query().map_err(CoreError::QueryCreation)? };
+        let version = context.version();
+        let supports_conservative = version.is_embedded
+            || (version.major, version.minor) >= (4, 3)
+            || context
+                .supported_extensions()
+                .contains("GL_ARB_ES3_compatibility");
+        Ok(Self {
+            context: context.clone(),
+            id,
+            target: if conservative && supports_conservative {
+                crate::context::ANY_SAMPLES_PASSED_CONSERVATIVE
+            } else {
+                crate::context::ANY_SAMPLES_PASSED
+            },
+            is_active: false,
+            is_pending: false,
+        })
+    }
+
+    ///
+    /// Starts the query. The result of a previous query is discarded if it has not been read yet.
+    ///
+    pub fn begin(&mut self) -> ThreeDResult<()> {
+        if !self.is_active {
+            unsafe {
+                self.context.begin_query(self.target, self.id);
+            }
+            self.is_active = true;
+            self.is_pending = false;
+        }
+        self.context.error_check()
+    }
+
+    ///
+    /// Ends the query. The result can be read using [OcclusionQuery::result] when it is available.
+    ///
+    pub fn end(&mut self) -> ThreeDResult<()> {
+        if self.is_active {
+            unsafe {
+                self.context.end_query(self.target);
+            }
+            self.is_active = false;
+            self.is_pending = true;
+        }
+        self.context.error_check()
+    }
+
+    ///
+    /// Determines whether any samples pass the depth and stencil tests when executing the draw calls issued in the callback,
+    /// which is the same as calling [OcclusionQuery::begin] before and [OcclusionQuery::end] after the callback.
+    ///
+    pub fn measure(&mut self, callback: impl FnOnce() -> ThreeDResult<()>) -> ThreeDResult<()> {
+        self.begin()?;
+        let result = callback();
+        self.end()?;
+        result
+    }
+
+    ///
+    /// Returns whether any samples passed if the result is available, otherwise `None`.
+    /// This method does not wait for the GPU to finish, so it should be called again later, for example the next frame, if the result is not yet available.
+    /// The result can only be read once, afterwards `None` is returned until a new query is ended.
+    ///
+    pub fn result(&mut self) -> Option<bool> {
+        if !self.is_pending {
+            return None;
+        }
+        unsafe {
+            if self
+                .context
+                .get_query_parameter_u32(self.id, crate::context::QUERY_RESULT_AVAILABLE)
+                == 0
+            {
+                return None;
+            }
+            self.is_pending = false;
+            Some(
+                self.context
+                    .get_query_parameter_u32(self.id, crate::context::QUERY_RESULT)
+                    != 0,
+            )
+        }
+    }
+
+    ///
+    /// Returns whether or not the query has ended but the result has not yet been read using [OcclusionQuery::result].
+    ///
+    pub fn is_pending(&self) -> bool {
+        self.is_pending
+    }
+}
+
+impl Drop for OcclusionQuery {
+    fn drop(&mut self) {
+        unsafe {
+            self.context.delete_query(self.id);
+        }
+    }
+}
@@ -19,6 +19,21 @@ pub struct RenderStates {
     ///
     pub depth_test: DepthTest,
 
+    ///
+    /// Defines the depth bias (also called polygon offset) in a render call.
+    /// The depth bias is added to the depth of each fragment before the depth test and before the depth is written
+    /// which is useful to avoid z-fighting between coplanar geometries and shadow acne when generating shadow maps.
+    ///
+    pub depth_bias: DepthBias,
+
+    ///
+    /// Defines the stencil test in a render call.
+    /// The stencil test determines whether or not a fragment from the current render call should be discarded
+    /// when comparing a reference value with the value in the stencil buffer
+    /// and how the value in the stencil buffer is updated afterwards.
+    ///
+    pub stencil_test: StencilTest,
+
     ///
     /// Defines which type of blending to use for a render call.
     /// Blending allows combining each color channel of a render call with the color already in the
@@ -38,6 +53,8 @@ impl Default for RenderStates {
         Self {
             write_mask: WriteMask::default(),
             depth_test: DepthTest::default(),
+            depth_bias: DepthBias::default(),
+            stencil_test: StencilTest::default(),
             blend: Blend::default(),
             cull: Cull::default(),
         }
@@ -90,6 +107,169 @@ impl Default for DepthTest {
     }
 }
 
+///
+/// Defines the depth bias (also called polygon offset), see [RenderStates].
+/// The depth of each fragment/pixel is offset by `factor * slope + units * r` where `slope` is the maximum depth slope of the triangle
+/// and `r` is the smallest value that is guaranteed to produce a resolvable difference in the depth target.
+/// Positive values push the fragments away from the camera and negative values pull them towards the camera.
+///
+/// **Note:** The depth bias only applies to filled triangles and has no effect if the render call is not writing to a depth texture.
+///
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct DepthBias {
+    /// Scales the maximum depth slope of the triangle.
+    pub factor: f32,
+    /// Scales the smallest resolvable depth difference.
+    pub units: f32,
+}
+
+impl DepthBias {
+    ///
+    /// No depth bias.
+    ///
+    pub const NONE: Self = Self {
+        factor: 0.0,
+        units: 0.0,
+    };
+
+    ///
+    /// Creates a new depth bias with the given factor and units.
+    ///
+    pub const fn new(factor: f32, units: f32) -> Self {
+        Self { factor, units }
+    }
+}
+
+impl Default for DepthBias {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+///
+/// Defines the stencil test in a render call, see [RenderStates].
+/// If enabled, the stencil value of the current fragment/pixel masked by `read_mask` is compared with the `reference` value masked by `read_mask`
+/// using the given `function` (for example, using [StencilFunction::Less], the test passes if `reference & read_mask < stencil & read_mask`).
+/// If the test fails, the fragment is discarded.
+/// Afterwards, the stencil value is updated based on the outcome of the stencil and depth test and only the bits defined by the `write_mask` is written.
+///
+/// **Note:** The stencil test always passes if the render call is not writing to a depth target with a stencil format (see [DepthFormat]).
+///
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum StencilTest {
+    /// The stencil test is enabled.
+    Enabled {
+        /// The function used for comparing the reference value and the stencil value.
+        function: StencilFunction,
+        /// The reference value which is compared with the stencil value.
+        reference: u8,
+        /// The mask that is applied to both the reference value and the stencil value before comparing them.
+        read_mask: u8,
+        /// The mask that defines which bits of the stencil value are written.
+        write_mask: u8,
+        /// The operation applied to the stencil value if the stencil test fails.
+        stencil_fail: StencilOperation,
+        /// The operation applied to the stencil value if the stencil test passes but the depth test fails.
+        depth_fail: StencilOperation,
+        /// The operation applied to the stencil value if both the stencil test and the depth test passes.
+        depth_pass: StencilOperation,
+    },
+    /// The stencil test is disabled, ie. the stencil test always passes and the stencil values are not modified.
+    Disabled,
+}
+
+impl StencilTest {
+    ///
+    /// Writes the given reference value to the stencil buffer wherever something is rendered.
+    ///
+    pub const fn write(reference: u8) -> Self {
+        Self::Enabled {
+            function: StencilFunction::Always,
+            reference,
+            read_mask: 0xFF,
+            write_mask: 0xFF,
+            stencil_fail: StencilOperation::Keep,
+            depth_fail: StencilOperation::Keep,
+            depth_pass: StencilOperation::Replace,
+        }
+    }
+
+    ///
+    /// Only renders where the stencil value is equal to the given reference value. The stencil values are not modified.
+    ///
+    pub const fn equal(reference: u8) -> Self {
+        Self::Enabled {
+            function: StencilFunction::Equal,
+            reference,
+            read_mask: 0xFF,
+            write_mask: 0x00,
+            stencil_fail: StencilOperation::Keep,
+            depth_fail: StencilOperation::Keep,
+            depth_pass: StencilOperation::Keep,
+        }
+    }
+
+    ///
+    /// Only renders where the stencil value is not equal to the given reference value. The stencil values are not modified.
+    ///
+    pub const fn not_equal(reference: u8) -> Self {
+        Self::Enabled {
+            function: StencilFunction::NotEqual,
+            reference,
+            read_mask: 0xFF,
+            write_mask: 0x00,
+            stencil_fail: StencilOperation::Keep,
+            depth_fail: StencilOperation::Keep,
+            depth_pass: StencilOperation::Keep,
+        }
+    }
+}
+
+impl Default for StencilTest {
+    fn default() -> Self {
+        Self::Disabled
+    }
+}
+
+///
+/// The function used for comparing the reference value with the stencil value in [StencilTest].
+///
+#[allow(missing_docs)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum StencilFunction {
+    Never,
+    Less,
+    Equal,
+    LessOrEqual,
+    Greater,
+    NotEqual,
+    GreaterOrEqual,
+    Always,
+}
+
+///
+/// The operation applied to the stencil value in [StencilTest].
+///
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum StencilOperation {
+    /// Keeps the current value.
+    Keep,
+    /// Sets the value to 0.
+    Zero,
+    /// Sets the value to the reference value.
+    Replace,
+    /// Increments the value, clamping it at the maximum value.
+    Increment,
+    /// Increments the value, wrapping it to 0 when exceeding the maximum value.
+    IncrementWrap,
+    /// Decrements the value, clamping it at 0.
+    Decrement,
+    /// Decrements the value, wrapping it to the maximum value when going below 0.
+    DecrementWrap,
+    /// Bitwise inverts the value.
+    Invert,
+}
+
 ///
 /// Defines which channels (red, green, blue, alpha and depth) to write to in a render call.
 ///
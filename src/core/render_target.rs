@@ -18,12 +18,20 @@ mod render_target_cube_map;
 #[doc(inline)]
 pub use render_target_cube_map::*;
 
+mod render_target3d;
+#[doc(inline)]
+pub use render_target3d::*;
+
+mod pixel_readback;
+#[doc(inline)]
+pub use pixel_readback::*;
+
 use crate::core::*;
 
 ///
-/// Defines which channels (red, green, blue, alpha and depth) to clear when starting to write to a
+/// Defines which channels (red, green, blue, alpha, depth and stencil) to clear when starting to write to a
 /// [RenderTarget] or the [Screen].
-/// If `None` then the channel is not cleared and if `Some(value)` the channel is cleared to that value (the value must be between 0 and 1).
+/// If `None` then the channel is not cleared and if `Some(value)` the channel is cleared to that value (the value must be between 0 and 1 except for the stencil value).
 ///
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub struct ClearState {
@@ -37,6 +45,8 @@ pub struct ClearState {
     pub alpha: Option<f32>,
     /// Defines the clear value for the depth channel. A value of 1 means a depth value equal to the far plane and 0 means a depth value equal to the near plane.
     pub depth: Option<f32>,
+    /// Defines the clear value for the stencil channel. Only has an effect if the depth target has a stencil format (see [DepthFormat]).
+    pub stencil: Option<u8>,
 }
 
 impl ClearState {
@@ -50,6 +60,7 @@ impl ClearState {
             blue: None,
             alpha: None,
             depth: None,
+            stencil: None,
         }
     }
 
@@ -63,6 +74,7 @@ impl ClearState {
             blue: None,
             alpha: None,
             depth: Some(depth),
+            stencil: None,
         }
     }
 
@@ -76,6 +88,7 @@ impl ClearState {
             blue: Some(blue),
             alpha: Some(alpha),
             depth: None,
+            stencil: None,
         }
     }
 
@@ -89,6 +102,21 @@ impl ClearState {
             blue: Some(blue),
             alpha: Some(alpha),
             depth: Some(depth),
+            stencil: None,
+        }
+    }
+
+    ///
+    /// Both the depth and the stencil will be cleared to the given values.
+    ///
+    pub const fn depth_and_stencil(depth: f32, stencil: u8) -> Self {
+        Self {
+            red: None,
+            green: None,
+            blue: None,
+            alpha: None,
+            depth: Some(depth),
+            stencil: Some(stencil),
         }
     }
 
@@ -105,6 +133,7 @@ impl ClearState {
                 || self.green.is_some()
                 || self.blue.is_some()
                 || self.alpha.is_some();
+            let mut mask = 0;
             if clear_color {
                 context.clear_color(
                     self.red.unwrap_or(0.0),
@@ -112,19 +141,21 @@ impl ClearState {
                     self.blue.unwrap_or(0.0),
                     self.alpha.unwrap_or(1.0),
                 );
+                mask |= crate::context::COLOR_BUFFER_BIT;
             }
             if let Some(depth) = self.depth {
                 context.clear_depth_f32(depth);
+                mask |= crate::context::DEPTH_BUFFER_BIT;
+            }
+            if let Some(stencil) = self.stencil {
+                context.stencil_mask(0xFF);
+                context.state_cache.borrow_mut().stencil_test = None;
+                context.clear_stencil(stencil as i32);
+                mask |= crate::context::STENCIL_BUFFER_BIT;
+            }
+            if mask != 0 {
+                context.clear(mask);
             }
-            context.clear(if clear_color && self.depth.is_some() {
-                crate::context::COLOR_BUFFER_BIT | crate::context::DEPTH_BUFFER_BIT
-            } else {
-                if clear_color {
-                    crate::context::COLOR_BUFFER_BIT
-                } else {
-                    crate::context::DEPTH_BUFFER_BIT
-                }
-            });
         }
     }
 }
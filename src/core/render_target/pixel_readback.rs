@@ -0,0 +1,135 @@
+use crate::core::render_target::*;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+///
+/// The colors of the pixels in a render target which are being read back from the GPU asynchronously,
+/// see for example [RenderTarget::read_color_async] and [ColorTarget::read_async].
+/// The pixels are copied into a buffer on the GPU and a [Fence] is used to find out when the copy is done,
+/// so that reading the pixels does not stall the CPU until the GPU has finished rendering.
+/// Use [PixelReadback::try_read] to poll for the result, for example once per frame, or [PixelReadback::read] to block until the result is available.
+///
+pub struct PixelReadback<T: TextureDataType> {
+    context: Context,
+    buffer: crate::context::Buffer,
+    fence: Fence,
+    width: u32,
+    height: u32,
+    byte_count: usize,
+    _data_type: PhantomData<T>,
+}
+
+impl<T: TextureDataType> PixelReadback<T> {
+    pub(in crate::core::render_target) fn new(
+        context: &Context,
+        scissor_box: ScissorBox,
+        data_size: usize,
+    ) -> ThreeDResult<Self> {
+        let byte_count = scissor_box.width as usize * scissor_box.height as usize * data_size;
+        let buffer = unsafe { context.create_buffer().map_err(CoreError::BufferCreation)? };
+        unsafe {
+            context.bind_buffer(crate::context::PIXEL_PACK_BUFFER, Some(buffer));
+            context.buffer_data_size(
+                crate::context::PIXEL_PACK_BUFFER,
+                byte_count as i32,
+                crate::context::STREAM_READ,
+            );
+            context.read_pixels(
+                scissor_box.x,
+                scissor_box.y,
+                scissor_box.width as i32,
+                scissor_box.height as i32,
+                format_from_data_type::<T>(),
+                T::data_type(),
+                crate::context::PixelPackData::BufferOffset(0),
+            );
+            context.bind_buffer(crate::context::PIXEL_PACK_BUFFER, None);
+        }
+        context.error_check()?;
+        Ok(Self {
+            context: context.clone(),
+            buffer,
+            fence: Fence::new(context)?,
+            width: scissor_box.width,
+            height: scissor_box.height,
+            byte_count,
+            _data_type: PhantomData,
+        })
+    }
+
+    ///
+    /// Returns whether or not the pixels are available, ie. whether [PixelReadback::try_read] will return the pixels.
+    /// Does not block.
+    ///
+    pub fn is_ready(&self) -> bool {
+        self.fence.is_signaled()
+    }
+
+    ///
+    /// Returns the pixels if they are available and otherwise `None`. Does not block.
+    ///
+    pub fn try_read(&self) -> ThreeDResult<Option<Vec<T>>> {
+        if self.is_ready() {
+            Ok(Some(self.read_buffer()?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    ///
+    /// Blocks until the pixels are available and returns them.
+    ///
+    /// **Note:** On web, it is not possible to wait for the fence, instead the pixels are read immediately which might stall until the GPU is done.
+    ///
+    pub fn read(&self) -> ThreeDResult<Vec<T>> {
+        #[cfg(not(target_arch = "wasm32"))]
+        while !self.fence.wait(Duration::from_secs(1))? {}
+        self.read_buffer()
+    }
+
+    ///
+    /// Blocks until the pixels are available or the timeout has expired and returns the pixels if they are available.
+    /// See [Fence::wait] for limitations of the timeout.
+    ///
+    pub fn read_with_timeout(&self, timeout: Duration) -> ThreeDResult<Option<Vec<T>>> {
+        if self.fence.wait(timeout)? {
+            Ok(Some(self.read_buffer()?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// The width of the area that is read.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The height of the area that is read.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    fn read_buffer(&self) -> ThreeDResult<Vec<T>> {
+        let mut bytes = vec![0u8; self.byte_count];
+        unsafe {
+            self.context
+                .bind_buffer(crate::context::PIXEL_PACK_BUFFER, Some(self.buffer));
+            self.context
+                .get_buffer_sub_data(crate::context::PIXEL_PACK_BUFFER, 0, &mut bytes);
+            self.context
+                .bind_buffer(crate::context::PIXEL_PACK_BUFFER, None);
+        }
+        self.context.error_check()?;
+        let mut pixels = from_byte_slice(&bytes).to_vec();
+        flip_y(&mut pixels, self.width as usize, self.height as usize);
+        Ok(pixels)
+    }
+}
+
+impl<T: TextureDataType> Drop for PixelReadback<T> {
+    fn drop(&mut self) {
+        unsafe {
+            self.context.delete_buffer(self.buffer);
+        }
+    }
+}
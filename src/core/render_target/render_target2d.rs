@@ -30,6 +30,14 @@ enum CT<'a> {
         side: CubeMapSide,
         mip_level: Option<u32>,
     },
+    Texture3D {
+        texture: &'a Texture3D,
+        slices: &'a [u32],
+        mip_level: Option<u32>,
+    },
+    Texture2DMultisample {
+        texture: &'a Texture2DMultisample,
+    },
 }
 
 impl<'a> ColorTarget<'a> {
@@ -44,6 +52,16 @@ impl<'a> ColorTarget<'a> {
         }
     }
 
+    pub(in crate::core) fn new_texture2d_multisample(
+        context: &Context,
+        texture: &'a Texture2DMultisample,
+    ) -> Self {
+        ColorTarget {
+            context: context.clone(),
+            target: CT::Texture2DMultisample { texture },
+        }
+    }
+
     pub(in crate::core) fn new_texture_cube_map(
         context: &Context,
         texture: &'a TextureCubeMap,
@@ -76,6 +94,22 @@ impl<'a> ColorTarget<'a> {
         }
     }
 
+    pub(in crate::core) fn new_texture3d(
+        context: &Context,
+        texture: &'a Texture3D,
+        slices: &'a [u32],
+        mip_level: Option<u32>,
+    ) -> Self {
+        ColorTarget {
+            context: context.clone(),
+            target: CT::Texture3D {
+                texture,
+                slices,
+                mip_level,
+            },
+        }
+    }
+
     ///
     /// Clears the color of this color target as defined by the given clear state.
     ///
@@ -95,6 +129,7 @@ impl<'a> ColorTarget<'a> {
             scissor_box,
             ClearState {
                 depth: None,
+                stencil: None,
                 ..clear_state
             },
         )?;
@@ -124,6 +159,7 @@ impl<'a> ColorTarget<'a> {
     ///
     /// Returns the colors of the pixels in this color target.
     /// The number of channels per pixel and the data format for each channel is specified by the generic parameter.
+    /// For integer textures, the data format must be the same integer type as the texture, for example `u32` for a texture with [u32] data.
     ///
     /// **Note:** On web, the data format needs to match the data format of the color texture.
     ///
@@ -134,6 +170,7 @@ impl<'a> ColorTarget<'a> {
     ///
     /// Returns the colors of the pixels in this color target inside the given scissor box.
     /// The number of channels per pixel and the data format for each channel is specified by the generic parameter.
+    /// For integer textures, the data format must be the same integer type as the texture, for example `u32` for a texture with [u32] data.
     ///
     /// **Note:** On web, the data format needs to match the data format of the color texture.
     ///
@@ -144,6 +181,51 @@ impl<'a> ColorTarget<'a> {
         self.as_render_target()?.read_color_partially(scissor_box)
     }
 
+    ///
+    /// Starts reading the colors of the pixels in this color target without waiting for the GPU to finish rendering,
+    /// see [RenderTarget::read_color_async].
+    ///
+    pub fn read_async<T: TextureDataType>(&self) -> ThreeDResult<PixelReadback<T>> {
+        self.read_partially_async(self.scissor_box())
+    }
+
+    ///
+    /// Starts reading the colors of the pixels in this color target inside the given scissor box without waiting for the GPU to finish rendering,
+    /// see [RenderTarget::read_color_partially_async].
+    ///
+    pub fn read_partially_async<T: TextureDataType>(
+        &self,
+        scissor_box: ScissorBox,
+    ) -> ThreeDResult<PixelReadback<T>> {
+        self.as_render_target()?
+            .read_color_partially_async(scissor_box)
+    }
+
+    ///
+    /// Resolves (copies) the colors in this color target into the given color target, for example from a [Texture2DMultisample] into a [Texture2D].
+    /// The two targets must have the same size and the same data format.
+    ///
+    pub fn resolve_to(&self, target: &ColorTarget) -> ThreeDResult<&Self> {
+        self.as_render_target()?.blit_to(
+            &target.as_render_target()?,
+            crate::context::COLOR_BUFFER_BIT,
+        )?;
+        target.generate_mip_maps();
+        Ok(self)
+    }
+
+    fn is_multisample(&self) -> bool {
+        matches!(self.target, CT::Texture2DMultisample { .. })
+    }
+
+    fn is_srgb(&self) -> bool {
+        match self.target {
+            CT::Texture2D { texture, .. } => texture.is_srgb(),
+            CT::TextureCubeMap { texture, .. } => texture.is_srgb(),
+            _ => false,
+        }
+    }
+
     ///
     /// Returns the width of the color target in texels.
     /// If using the zero mip level of the underlying texture, then this is simply the width of that texture, otherwise it is the width of the given mip level.
@@ -157,6 +239,10 @@ impl<'a> ColorTarget<'a> {
             CT::TextureCubeMap {
                 texture, mip_level, ..
             } => size_with_mip(texture.width(), mip_level),
+            CT::Texture3D {
+                texture, mip_level, ..
+            } => size_with_mip(texture.width(), mip_level),
+            CT::Texture2DMultisample { texture } => texture.width(),
         }
     }
 
@@ -173,6 +259,10 @@ impl<'a> ColorTarget<'a> {
             CT::TextureCubeMap {
                 texture, mip_level, ..
             } => size_with_mip(texture.height(), mip_level),
+            CT::Texture3D {
+                texture, mip_level, ..
+            } => size_with_mip(texture.height(), mip_level),
+            CT::Texture2DMultisample { texture } => texture.height(),
         }
     }
 
@@ -208,6 +298,14 @@ impl<'a> ColorTarget<'a> {
                     texture.generate_mip_maps()
                 }
             }
+            CT::Texture3D {
+                texture, mip_level, ..
+            } => {
+                if mip_level.is_none() {
+                    texture.generate_mip_maps()
+                }
+            }
+            CT::Texture2DMultisample { .. } => {}
         }
     }
 
@@ -243,6 +341,24 @@ impl<'a> ColorTarget<'a> {
                 context.draw_buffers(&[crate::context::COLOR_ATTACHMENT0]);
                 texture.bind_as_color_target(side, 0, mip_level.unwrap_or(0));
             },
+            CT::Texture3D {
+                texture,
+                slices,
+                mip_level,
+            } => unsafe {
+                context.draw_buffers(
+                    &(0..slices.len())
+                        .map(|i| crate::context::COLOR_ATTACHMENT0 + i as u32)
+                        .collect::<Vec<u32>>(),
+                );
+                for (channel, slice) in slices.iter().enumerate() {
+                    texture.bind_as_color_target(*slice, channel as u32, mip_level.unwrap_or(0));
+                }
+            },
+            CT::Texture2DMultisample { texture } => unsafe {
+                context.draw_buffers(&[crate::context::COLOR_ATTACHMENT0]);
+                texture.bind_as_color_target(0);
+            },
         }
     }
 }
@@ -272,6 +388,9 @@ enum DT<'a> {
         texture: &'a DepthTargetTextureCubeMap,
         side: CubeMapSide,
     },
+    Texture2DMultisample {
+        texture: &'a DepthTargetTexture2DMultisample,
+    },
 }
 
 impl<'a> DepthTarget<'a> {
@@ -285,6 +404,16 @@ impl<'a> DepthTarget<'a> {
         }
     }
 
+    pub(in crate::core) fn new_texture2d_multisample(
+        context: &Context,
+        texture: &'a DepthTargetTexture2DMultisample,
+    ) -> Self {
+        Self {
+            context: context.clone(),
+            target: DT::Texture2DMultisample { texture },
+        }
+    }
+
     pub(in crate::core) fn new_texture_cube_map(
         context: &Context,
         texture: &'a DepthTargetTextureCubeMap,
@@ -308,14 +437,14 @@ impl<'a> DepthTarget<'a> {
     }
 
     ///
-    /// Clears the depth of this depth target as defined by the given clear state.
+    /// Clears the depth and stencil of this depth target as defined by the given clear state.
     ///
     pub fn clear(&self, clear_state: ClearState) -> ThreeDResult<&Self> {
         self.clear_partially(self.scissor_box(), clear_state)
     }
 
     ///
-    /// Clears the depth of the part of this depth target that is inside the given scissor box.
+    /// Clears the depth and stencil of the part of this depth target that is inside the given scissor box.
     ///
     pub fn clear_partially(
         &self,
@@ -326,6 +455,7 @@ impl<'a> DepthTarget<'a> {
             scissor_box,
             ClearState {
                 depth: clear_state.depth,
+                stencil: clear_state.stencil,
                 ..ClearState::none()
             },
         )?;
@@ -368,6 +498,22 @@ impl<'a> DepthTarget<'a> {
         self.as_render_target()?.read_depth_partially(scissor_box)
     }
 
+    ///
+    /// Resolves (copies) the depth and stencil values in this depth target into the given depth target, for example from a [DepthTargetTexture2DMultisample] into a [DepthTargetTexture2D].
+    /// The two targets must have the same size and the same [DepthFormat].
+    ///
+    pub fn resolve_to(&self, target: &DepthTarget) -> ThreeDResult<&Self> {
+        self.as_render_target()?.blit_to(
+            &target.as_render_target()?,
+            crate::context::DEPTH_BUFFER_BIT,
+        )?;
+        Ok(self)
+    }
+
+    fn is_multisample(&self) -> bool {
+        matches!(self.target, DT::Texture2DMultisample { .. })
+    }
+
     fn as_render_target(&self) -> ThreeDResult<RenderTarget<'a>> {
         RenderTarget::new_depth(self.clone())
     }
@@ -380,6 +526,7 @@ impl<'a> DepthTarget<'a> {
             DT::Texture2D { texture, .. } => texture.width(),
             DT::Texture2DArray { texture, .. } => texture.width(),
             DT::TextureCubeMap { texture, .. } => texture.width(),
+            DT::Texture2DMultisample { texture } => texture.width(),
         }
     }
 
@@ -391,6 +538,7 @@ impl<'a> DepthTarget<'a> {
             DT::Texture2D { texture, .. } => texture.height(),
             DT::Texture2DArray { texture, .. } => texture.height(),
             DT::TextureCubeMap { texture, .. } => texture.height(),
+            DT::Texture2DMultisample { texture } => texture.height(),
         }
     }
 
@@ -412,6 +560,9 @@ impl<'a> DepthTarget<'a> {
             DT::TextureCubeMap { texture, side } => {
                 texture.bind_as_depth_target(*side);
             }
+            DT::Texture2DMultisample { texture } => {
+                texture.bind_as_depth_target();
+            }
         }
     }
 }
@@ -531,16 +682,7 @@ impl<'a> RenderTarget<'a> {
         &self,
         scissor_box: ScissorBox,
     ) -> ThreeDResult<Vec<T>> {
-        if self.id.is_some() && self.color.is_none() {
-            Err(CoreError::RenderTargetRead("color".to_string()))?;
-        }
-        self.bind(crate::context::DRAW_FRAMEBUFFER)?;
-        self.bind(crate::context::READ_FRAMEBUFFER)?;
-        let mut data_size = std::mem::size_of::<T>();
-        // On web, the format needs to be RGBA if the data type is byte.
-        if data_size / T::size() as usize == 1 {
-            data_size *= 4 / T::size() as usize
-        }
+        let data_size = self.prepare_color_read::<T>()?;
         let mut bytes =
             vec![0u8; scissor_box.width as usize * scissor_box.height as usize * data_size];
         unsafe {
@@ -564,6 +706,54 @@ impl<'a> RenderTarget<'a> {
         Ok(pixels)
     }
 
+    ///
+    /// Starts reading the colors of the pixels in this render target without waiting for the GPU to finish rendering
+    /// and returns a [PixelReadback] which can be used to get the pixels when they are available.
+    /// The number of channels per pixel and the data format for each channel is specified by the generic parameter.
+    ///
+    /// **Note:** On web, the data format needs to match the data format of the color texture.
+    ///
+    pub fn read_color_async<T: TextureDataType>(&self) -> ThreeDResult<PixelReadback<T>> {
+        self.read_color_partially_async(self.scissor_box())
+    }
+
+    ///
+    /// Starts reading the colors of the pixels in this render target inside the given scissor box without waiting for the GPU to finish rendering
+    /// and returns a [PixelReadback] which can be used to get the pixels when they are available.
+    /// The number of channels per pixel and the data format for each channel is specified by the generic parameter.
+    ///
+    /// **Note:** On web, the data format needs to match the data format of the color texture.
+    ///
+    pub fn read_color_partially_async<T: TextureDataType>(
+        &self,
+        scissor_box: ScissorBox,
+    ) -> ThreeDResult<PixelReadback<T>> {
+        let data_size = self.prepare_color_read::<T>()?;
+        PixelReadback::new(&self.context, scissor_box, data_size)
+    }
+
+    fn prepare_color_read<T: TextureDataType>(&self) -> ThreeDResult<usize> {
+        if self.id.is_some() && self.color.is_none() {
+            Err(CoreError::RenderTargetRead("color".to_string()))?;
+        }
+        if self
+            .color
+            .as_ref()
+            .map(|c| c.is_multisample())
+            .unwrap_or(false)
+        {
+            Err(CoreError::MultisampleRead("color".to_string()))?;
+        }
+        self.bind(crate::context::DRAW_FRAMEBUFFER)?;
+        self.bind(crate::context::READ_FRAMEBUFFER)?;
+        let mut data_size = std::mem::size_of::<T>();
+        // On web, the format needs to be RGBA if the data type is byte.
+        if data_size / T::size() as usize == 1 {
+            data_size *= 4 / T::size() as usize
+        }
+        Ok(data_size)
+    }
+
     ///
     /// Returns the depth values in this render target.
     ///
@@ -580,6 +770,14 @@ impl<'a> RenderTarget<'a> {
         if self.id.is_some() && self.depth.is_none() {
             Err(CoreError::RenderTargetRead("depth".to_string()))?;
         }
+        if self
+            .depth
+            .as_ref()
+            .map(|d| d.is_multisample())
+            .unwrap_or(false)
+        {
+            Err(CoreError::MultisampleRead("depth".to_string()))?;
+        }
         self.bind(crate::context::DRAW_FRAMEBUFFER)?;
         self.bind(crate::context::READ_FRAMEBUFFER)?;
         let mut pixels = vec![0u8; scissor_box.width as usize * scissor_box.height as usize * 4];
@@ -642,6 +840,32 @@ impl<'a> RenderTarget<'a> {
         })
     }
 
+    ///
+    /// Resolves (copies) the color, depth and stencil of this render target into the given render target,
+    /// for example from a render target consisting of a [Texture2DMultisample] and a [DepthTargetTexture2DMultisample]
+    /// into a render target consisting of a [Texture2D] and a [DepthTargetTexture2D].
+    /// Only the channels that are present in both render targets are resolved.
+    /// The two render targets must have the same size and the same data formats.
+    ///
+    pub fn resolve_to(&self, target: &RenderTarget) -> ThreeDResult<&Self> {
+        let mut mask = 0;
+        if (self.id.is_none() || self.color.is_some())
+            && (target.id.is_none() || target.color.is_some())
+        {
+            mask |= crate::context::COLOR_BUFFER_BIT;
+        }
+        if (self.id.is_none() || self.depth.is_some())
+            && (target.id.is_none() || target.depth.is_some())
+        {
+            mask |= crate::context::DEPTH_BUFFER_BIT | crate::context::STENCIL_BUFFER_BIT;
+        }
+        self.blit_to(target, mask)?;
+        if let Some(ref color) = target.color {
+            color.generate_mip_maps();
+        }
+        Ok(self)
+    }
+
     ///
     /// Returns the scissor box that encloses the entire target.
     ///
@@ -649,6 +873,29 @@ impl<'a> RenderTarget<'a> {
         ScissorBox::new_at_origo(self.width, self.height)
     }
 
+    fn blit_to(&self, target: &RenderTarget, mask: u32) -> ThreeDResult<()> {
+        self.bind(crate::context::DRAW_FRAMEBUFFER)?;
+        self.bind(crate::context::READ_FRAMEBUFFER)?;
+        target.bind(crate::context::DRAW_FRAMEBUFFER)?;
+        self.context.set_scissor(ScissorBox::new_at_origo(0, 0));
+        self.context.set_write_mask(WriteMask::COLOR_AND_DEPTH);
+        unsafe {
+            self.context.blit_framebuffer(
+                0,
+                0,
+                self.width as i32,
+                self.height as i32,
+                0,
+                0,
+                target.width as i32,
+                target.height as i32,
+                mask,
+                crate::context::NEAREST,
+            );
+        }
+        self.context.error_check()
+    }
+
     fn new_color(color: ColorTarget<'a>) -> ThreeDResult<Self> {
         let width = color.width();
         let height = color.height();
@@ -678,6 +925,14 @@ impl<'a> RenderTarget<'a> {
     fn bind(&self, target: u32) -> ThreeDResult<()> {
         unsafe {
             self.context.bind_framebuffer(target, self.id);
+            // On web and OpenGL ES, writing to an sRGB target always converts from linear to sRGB.
+            if !self.context.version().is_embedded {
+                if self.color.as_ref().map(|c| c.is_srgb()).unwrap_or(false) {
+                    self.context.enable(crate::context::FRAMEBUFFER_SRGB);
+                } else {
+                    self.context.disable(crate::context::FRAMEBUFFER_SRGB);
+                }
+            }
         }
         if let Some(ref color) = self.color {
             color.bind(&self.context);
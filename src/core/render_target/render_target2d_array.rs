@@ -78,16 +78,15 @@ impl<'a, 'b> RenderTargetArray<'a, 'b> {
         render: impl FnOnce() -> ThreeDResult<()>,
     ) -> ThreeDResult<()> {
         self.bind(Some(color_layers), Some(depth_layer))?;
-        clear(
-            &self.context,
-            &ClearState {
-                red: self.color_texture.as_ref().and(clear_state.red),
-                green: self.color_texture.as_ref().and(clear_state.green),
-                blue: self.color_texture.as_ref().and(clear_state.blue),
-                alpha: self.color_texture.as_ref().and(clear_state.alpha),
-                depth: self.depth_texture.as_ref().and(clear_state.depth),
-            },
-        );
+        ClearState {
+            red: self.color_texture.as_ref().and(clear_state.red),
+            green: self.color_texture.as_ref().and(clear_state.green),
+            blue: self.color_texture.as_ref().and(clear_state.blue),
+            alpha: self.color_texture.as_ref().and(clear_state.alpha),
+            depth: self.depth_texture.as_ref().and(clear_state.depth),
+            stencil: self.depth_texture.as_ref().and(clear_state.stencil),
+        }
+        .apply(&self.context);
         render()?;
         if let Some(ref color_texture) = self.color_texture {
             color_texture.generate_mip_maps();
@@ -133,39 +132,3 @@ impl Drop for RenderTargetArray<'_, '_> {
         }
     }
 }
-
-fn clear(context: &Context, clear_state: &ClearState) {
-    context.set_write_mask(WriteMask {
-        red: clear_state.red.is_some(),
-        green: clear_state.green.is_some(),
-        blue: clear_state.blue.is_some(),
-        alpha: clear_state.alpha.is_some(),
-        depth: clear_state.depth.is_some(),
-    });
-    let clear_color = clear_state.red.is_some()
-        || clear_state.green.is_some()
-        || clear_state.blue.is_some()
-        || clear_state.alpha.is_some();
-    unsafe {
-        if clear_color {
-            context.clear_color(
-                clear_state.red.unwrap_or(0.0),
-                clear_state.green.unwrap_or(0.0),
-                clear_state.blue.unwrap_or(0.0),
-                clear_state.alpha.unwrap_or(1.0),
-            );
-        }
-        if let Some(depth) = clear_state.depth {
-            context.clear_depth_f32(depth);
-        }
-        context.clear(if clear_color && clear_state.depth.is_some() {
-            crate::context::COLOR_BUFFER_BIT | crate::context::DEPTH_BUFFER_BIT
-        } else {
-            if clear_color {
-                crate::context::COLOR_BUFFER_BIT
-            } else {
-                crate::context::DEPTH_BUFFER_BIT
-            }
-        });
-    }
-}
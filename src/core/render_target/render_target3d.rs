@@ -0,0 +1,96 @@
+use crate::core::render_target::*;
+
+///
+/// Adds additional functionality to write to slices of a [Texture3D] and to a [DepthTargetTexture2D] at the same time,
+/// in the same way as [RenderTargetArray] does for a [Texture2DArray].
+/// It purely adds functionality, so it can be created each time it is needed, the data is saved in the textures.
+///
+pub struct RenderTarget3D<'a, 'b> {
+    context: Context,
+    id: crate::context::Framebuffer,
+    color_texture: &'a mut Texture3D,
+    depth_texture: Option<&'b mut DepthTargetTexture2D>,
+}
+
+impl<'a, 'b> RenderTarget3D<'a, 'b> {
+    ///
+    /// Constructs a new render target that enables rendering into slices of the given [Texture3D]
+    /// while depth testing against the given [DepthTargetTexture2D], which must have the same width and height as the 3D texture.
+    ///
+    pub fn new(
+        context: &Context,
+        color_texture: &'a mut Texture3D,
+        depth_texture: &'b mut DepthTargetTexture2D,
+    ) -> ThreeDResult<Self> {
+        Ok(Self {
+            context: context.clone(),
+            id: new_framebuffer(context)?,
+            color_texture,
+            depth_texture: Some(depth_texture),
+        })
+    }
+
+    ///
+    /// Constructs a new render target that enables rendering into slices of the given [Texture3D].
+    ///
+    pub fn new_color(context: &Context, color_texture: &'a mut Texture3D) -> ThreeDResult<Self> {
+        Ok(Self {
+            context: context.clone(),
+            id: new_framebuffer(context)?,
+            color_texture,
+            depth_texture: None,
+        })
+    }
+
+    ///
+    /// Renders whatever rendered in the `render` closure into the slices (along the z-axis) of the 3D texture defined by the input parameter `color_slices`.
+    /// Output at location *i* defined in the fragment shader is written to the slice at the *ith* index in `color_slices`.
+    /// Before writing, the textures are cleared based on the given clear state.
+    ///
+    pub fn write(
+        &self,
+        color_slices: &[u32],
+        clear_state: ClearState,
+        render: impl FnOnce() -> ThreeDResult<()>,
+    ) -> ThreeDResult<()> {
+        self.bind(color_slices)?;
+        ClearState {
+            depth: self.depth_texture.as_ref().and(clear_state.depth),
+            stencil: self.depth_texture.as_ref().and(clear_state.stencil),
+            ..clear_state
+        }
+        .apply(&self.context);
+        render()?;
+        self.color_texture.generate_mip_maps();
+        Ok(())
+    }
+
+    fn bind(&self, color_slices: &[u32]) -> ThreeDResult<()> {
+        unsafe {
+            self.context
+                .bind_framebuffer(crate::context::DRAW_FRAMEBUFFER, Some(self.id));
+            self.context.draw_buffers(
+                &(0..color_slices.len())
+                    .map(|i| crate::context::COLOR_ATTACHMENT0 + i as u32)
+                    .collect::<Vec<u32>>(),
+            );
+        }
+        for (channel, slice) in color_slices.iter().enumerate() {
+            self.color_texture
+                .bind_as_color_target(*slice, channel as u32, 0);
+        }
+        if let Some(ref depth_texture) = self.depth_texture {
+            depth_texture.bind_as_depth_target();
+        }
+        self.context.framebuffer_check()?;
+        self.context.error_check()
+    }
+}
+
+impl Drop for RenderTarget3D<'_, '_> {
+    fn drop(&mut self) {
+        unsafe {
+            self.context.delete_framebuffer(self.id);
+        }
+    }
+}
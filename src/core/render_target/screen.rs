@@ -32,6 +32,17 @@ impl Screen {
         RenderTarget::screen(context, 0, 0).read_color_partially(viewport.into())
     }
 
+    ///
+    /// Starts reading the RGBA color values from the screen without waiting for the GPU to finish rendering,
+    /// see [RenderTarget::read_color_async].
+    ///
+    pub fn read_color_async(
+        context: &Context,
+        viewport: Viewport,
+    ) -> ThreeDResult<PixelReadback<[u8; 4]>> {
+        RenderTarget::screen(context, 0, 0).read_color_partially_async(viewport.into())
+    }
+
     ///
     /// Returns the depth values from the screen as a list of 32-bit floats.
     /// Only available on desktop.
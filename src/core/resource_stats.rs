@@ -0,0 +1,170 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+///
+/// The kind of a GPU resource tracked by the [Context](crate::core::Context), see [Context::resource_stats](crate::core::Context::resource_stats).
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ResourceKind {
+    /// A [Texture2D](crate::core::Texture2D).
+    Texture2D,
+    /// A [Texture2DArray](crate::core::Texture2DArray).
+    Texture2DArray,
+    /// A [TextureCubeMap](crate::core::TextureCubeMap).
+    TextureCubeMap,
+    /// A [Texture3D](crate::core::Texture3D).
+    Texture3D,
+    /// A [DepthTargetTexture2D](crate::core::DepthTargetTexture2D), [DepthTargetTexture2DArray](crate::core::DepthTargetTexture2DArray) or [DepthTargetTextureCubeMap](crate::core::DepthTargetTextureCubeMap).
+    DepthTexture,
+    /// A [Texture2DMultisample](crate::core::Texture2DMultisample) or [DepthTargetTexture2DMultisample](crate::core::DepthTargetTexture2DMultisample).
+    MultisampleTexture,
+    /// A [VertexBuffer](crate::core::VertexBuffer).
+    VertexBuffer,
+    /// An [InstanceBuffer](crate::core::InstanceBuffer).
+    InstanceBuffer,
+    /// An [ElementBuffer](crate::core::ElementBuffer).
+    ElementBuffer,
+    /// A [UniformBuffer](crate::core::UniformBuffer).
+    UniformBuffer,
+    /// A [Program](crate::core::Program).
+    Program,
+}
+
+///
+/// Information about a GPU resource which is currently alive, see [ResourceStats].
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResourceInfo {
+    /// The kind of resource.
+    pub kind: ResourceKind,
+    /// An estimate of the GPU memory used by the resource in bytes. Programs are always reported with a size of zero.
+    pub byte_size: usize,
+    /// The debug label of the resource, if one has been set, for example using [Texture2D::set_label](crate::core::Texture2D::set_label).
+    pub label: Option<String>,
+}
+
+///
+/// The number of resources of a specific kind and an estimate of the GPU memory they use, see [ResourceStats::kind].
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ResourceKindStats {
+    /// The number of resources which are alive.
+    pub count: usize,
+    /// An estimate of the GPU memory used by the resources in bytes.
+    pub byte_size: usize,
+}
+
+///
+/// A snapshot of the GPU resources which are alive, see [Context::resource_stats](crate::core::Context::resource_stats).
+/// Use the debug labels of the resources to find out which part of an application is responsible for a resource that should have been dropped.
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResourceStats {
+    /// All resources which are alive in the order they were created.
+    pub resources: Vec<ResourceInfo>,
+    /// The number of programs cached by [Context::program](crate::core::Context::program). These programs are also included in [ResourceStats::resources].
+    pub cached_programs: usize,
+    /// The number of effects cached by [Context::effect](crate::core::Context::effect). The programs used by these effects are also included in [ResourceStats::resources].
+    pub cached_effects: usize,
+}
+
+impl ResourceStats {
+    ///
+    /// Returns the number of resources of the given kind and an estimate of the GPU memory they use.
+    ///
+    pub fn kind(&self, kind: ResourceKind) -> ResourceKindStats {
+        let mut stats = ResourceKindStats::default();
+        for resource in self.resources.iter().filter(|r| r.kind == kind) {
+            stats.count += 1;
+            stats.byte_size += resource.byte_size;
+        }
+        stats
+    }
+
+    ///
+    /// Returns an estimate of the GPU memory used by all resources in bytes.
+    ///
+    pub fn total_byte_size(&self) -> usize {
+        self.resources.iter().map(|r| r.byte_size).sum()
+    }
+
+    ///
+    /// Returns the resources with the given debug label.
+    ///
+    pub fn labeled<'a>(&'a self, label: &'a str) -> impl Iterator<Item = &'a ResourceInfo> + 'a {
+        self.resources
+            .iter()
+            .filter(move |r| r.label.as_deref() == Some(label))
+    }
+}
+
+#[derive(Default)]
+pub(super) struct ResourceRegistry {
+    next_id: u64,
+    resources: BTreeMap<u64, ResourceInfo>,
+}
+
+impl ResourceRegistry {
+    pub(super) fn track(
+        registry: &Rc<RefCell<Self>>,
+        kind: ResourceKind,
+        byte_size: usize,
+    ) -> ResourceHandle {
+        let mut r = registry.borrow_mut();
+        let id = r.next_id;
+        r.next_id += 1;
+        r.resources.insert(
+            id,
+            ResourceInfo {
+                kind,
+                byte_size,
+                label: None,
+            },
+        );
+        ResourceHandle {
+            registry: registry.clone(),
+            id,
+        }
+    }
+
+    pub(super) fn resources(&self) -> Vec<ResourceInfo> {
+        self.resources.values().cloned().collect()
+    }
+}
+
+///
+/// Keeps a resource registered in the [Context](crate::core::Context) until it is dropped.
+///
+pub(super) struct ResourceHandle {
+    registry: Rc<RefCell<ResourceRegistry>>,
+    id: u64,
+}
+
+impl ResourceHandle {
+    pub fn set_byte_size(&self, byte_size: usize) {
+        if let Some(info) = self.registry.borrow_mut().resources.get_mut(&self.id) {
+            info.byte_size = byte_size;
+        }
+    }
+
+    pub fn set_label(&self, label: &str) {
+        if let Some(info) = self.registry.borrow_mut().resources.get_mut(&self.id) {
+            info.label = Some(label.to_string());
+        }
+    }
+
+    pub fn label(&self) -> Option<String> {
+        self.registry
+            .borrow()
+            .resources
+            .get(&self.id)
+            .and_then(|info| info.label.clone())
+    }
+}
+
+impl Drop for ResourceHandle {
+    fn drop(&mut self) {
+        self.registry.borrow_mut().resources.remove(&self.id);
+    }
+}
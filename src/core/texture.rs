@@ -29,14 +29,36 @@ mod depth_target_texture_cube_map;
 #[doc(inline)]
 pub use depth_target_texture_cube_map::*;
 
+mod texture2d_multisample;
+#[doc(inline)]
+pub use texture2d_multisample::*;
+
+mod depth_target_texture2d_multisample;
+#[doc(inline)]
+pub use depth_target_texture2d_multisample::*;
+
+mod sampler;
+#[doc(inline)]
+pub use sampler::*;
+
 use data_type::*;
 pub use three_d_asset::texture::{
     Interpolation, Texture2D as CpuTexture, Texture3D as CpuTexture3D, TextureData, Wrapping,
 };
 
+///
 /// The basic data type used for each channel of each pixel in a texture.
+/// Bytes ([u8]) are normalized to the range `[0, 1]` when sampled in a shader, the same is the case for floating point values.
+/// The other integer types ([u16], [u32], [i8], [i16] and [i32]) are not normalized and must be sampled using an unsigned or signed integer sampler,
+/// for example `usampler2D` or `isampler2D`. Integer textures only support [Interpolation::Nearest] and no mip maps.
+///
 pub trait TextureDataType: DataType {}
 impl TextureDataType for u8 {}
+impl TextureDataType for u16 {}
+impl TextureDataType for u32 {}
+impl TextureDataType for i8 {}
+impl TextureDataType for i16 {}
+impl TextureDataType for i32 {}
 impl TextureDataType for f16 {}
 impl TextureDataType for f32 {}
 
@@ -52,6 +74,59 @@ impl TextureDataType for Quat {}
 
 impl<T: TextureDataType + ?Sized> TextureDataType for &T {}
 
+///
+/// Encodes the given [CpuTexture] as a Radiance HDR (.hdr) image and returns the bytes, which can for example be saved to disk.
+/// In contrast to saving as for example PNG, the high dynamic range of 16 and 32 bit float data is preserved.
+/// Byte data is converted to the range `[0, 1]`, single channel data is stored as gray scale and missing channels are otherwise set to zero.
+/// The alpha channel is discarded.
+///
+#[cfg(all(feature = "image-io", not(target_arch = "wasm32")))]
+pub fn hdr_image_from_cpu_texture(cpu_texture: &CpuTexture) -> ThreeDResult<Vec<u8>> {
+    fn rgb(r: f32, g: f32, b: f32) -> image::Rgb<f32> {
+        image::Rgb([r, g, b])
+    }
+    let b = |v: u8| v as f32 / 255.0;
+    let pixels: Vec<image::Rgb<f32>> = match &cpu_texture.data {
+        TextureData::RU8(data) => data.iter().map(|v| rgb(b(*v), b(*v), b(*v))).collect(),
+        TextureData::RgU8(data) => data.iter().map(|v| rgb(b(v[0]), b(v[1]), 0.0)).collect(),
+        TextureData::RgbU8(data) => data
+            .iter()
+            .map(|v| rgb(b(v[0]), b(v[1]), b(v[2])))
+            .collect(),
+        TextureData::RgbaU8(data) => data
+            .iter()
+            .map(|v| rgb(b(v[0]), b(v[1]), b(v[2])))
+            .collect(),
+        TextureData::RF16(data) => data
+            .iter()
+            .map(|v| rgb(v.to_f32(), v.to_f32(), v.to_f32()))
+            .collect(),
+        TextureData::RgF16(data) => data
+            .iter()
+            .map(|v| rgb(v[0].to_f32(), v[1].to_f32(), 0.0))
+            .collect(),
+        TextureData::RgbF16(data) => data
+            .iter()
+            .map(|v| rgb(v[0].to_f32(), v[1].to_f32(), v[2].to_f32()))
+            .collect(),
+        TextureData::RgbaF16(data) => data
+            .iter()
+            .map(|v| rgb(v[0].to_f32(), v[1].to_f32(), v[2].to_f32()))
+            .collect(),
+        TextureData::RF32(data) => data.iter().map(|v| rgb(*v, *v, *v)).collect(),
+        TextureData::RgF32(data) => data.iter().map(|v| rgb(v[0], v[1], 0.0)).collect(),
+        TextureData::RgbF32(data) => data.iter().map(|v| rgb(v[0], v[1], v[2])).collect(),
+        TextureData::RgbaF32(data) => data.iter().map(|v| rgb(v[0], v[1], v[2])).collect(),
+    };
+    let mut bytes = Vec::new();
+    image::codecs::hdr::HdrEncoder::new(&mut bytes).encode(
+        &pixels,
+        cpu_texture.width as usize,
+        cpu_texture.height as usize,
+    )?;
+    Ok(bytes)
+}
+
 use crate::core::*;
 
 // COMMON TEXTURE FUNCTIONS
@@ -64,76 +139,188 @@ fn generate(context: &Context) -> ThreeDResult<crate::context::Texture> {
     }
 }
 
+fn generate_renderbuffer(context: &Context) -> ThreeDResult<crate::context::Renderbuffer> {
+    unsafe {
+        Ok(context
+            .create_renderbuffer()
+            .map_err(CoreError::TextureCreation)?)
+    }
+}
+
+fn check_number_of_samples(context: &Context, number_of_samples: u32) -> ThreeDResult<()> {
+    let max_samples = unsafe { context.get_parameter_i32(crate::context::MAX_SAMPLES) } as u32;
+    if number_of_samples == 0 || number_of_samples > max_samples {
+        Err(CoreError::InvalidNumberOfSamples(
+            number_of_samples,
+            max_samples,
+        ))?;
+    }
+    Ok(())
+}
+
 fn set_parameters(
     context: &Context,
     target: u32,
-    min_filter: Interpolation,
-    mag_filter: Interpolation,
-    mip_map_filter: Option<Interpolation>,
-    wrap_s: Wrapping,
-    wrap_t: Wrapping,
-    wrap_r: Option<Wrapping>,
+    sampler: &Sampler,
+    number_of_mip_maps: u32,
 ) -> ThreeDResult<()> {
+    let mip_map_filter = if number_of_mip_maps == 1 {
+        None
+    } else {
+        sampler.mip_map_filter
+    };
     unsafe {
-        match mip_map_filter {
-            None => context.tex_parameter_i32(
-                target,
-                crate::context::TEXTURE_MIN_FILTER,
-                interpolation_from(min_filter),
-            ),
-            Some(Interpolation::Nearest) => {
-                if min_filter == Interpolation::Nearest {
-                    context.tex_parameter_i32(
-                        target,
-                        crate::context::TEXTURE_MIN_FILTER,
-                        crate::context::NEAREST_MIPMAP_NEAREST as i32,
-                    );
-                } else {
-                    context.tex_parameter_i32(
-                        target,
-                        crate::context::TEXTURE_MIN_FILTER,
-                        crate::context::LINEAR_MIPMAP_NEAREST as i32,
-                    )
-                }
-            }
-            Some(Interpolation::Linear) => {
-                if min_filter == Interpolation::Nearest {
-                    context.tex_parameter_i32(
-                        target,
-                        crate::context::TEXTURE_MIN_FILTER,
-                        crate::context::NEAREST_MIPMAP_LINEAR as i32,
-                    );
-                } else {
-                    context.tex_parameter_i32(
-                        target,
-                        crate::context::TEXTURE_MIN_FILTER,
-                        crate::context::LINEAR_MIPMAP_LINEAR as i32,
-                    )
-                }
-            }
-        }
+        context.tex_parameter_i32(
+            target,
+            crate::context::TEXTURE_MIN_FILTER,
+            min_filter_from(sampler.min_filter, mip_map_filter),
+        );
         context.tex_parameter_i32(
             target,
             crate::context::TEXTURE_MAG_FILTER,
-            interpolation_from(mag_filter),
+            interpolation_from(sampler.mag_filter),
         );
         context.tex_parameter_i32(
             target,
             crate::context::TEXTURE_WRAP_S,
-            wrapping_from(wrap_s),
+            wrapping_from(sampler.wrap_s),
         );
         context.tex_parameter_i32(
             target,
             crate::context::TEXTURE_WRAP_T,
-            wrapping_from(wrap_t),
+            wrapping_from(sampler.wrap_t),
         );
-        if let Some(r) = wrap_r {
-            context.tex_parameter_i32(target, crate::context::TEXTURE_WRAP_R, wrapping_from(r));
+        context.tex_parameter_i32(
+            target,
+            crate::context::TEXTURE_WRAP_R,
+            wrapping_from(sampler.wrap_r),
+        );
+        if context
+            .supported_extensions()
+            .iter()
+            .any(|e| e.ends_with("texture_filter_anisotropic"))
+        {
+            let max_anisotropy =
+                context.get_parameter_f32(crate::context::MAX_TEXTURE_MAX_ANISOTROPY_EXT);
+            context.tex_parameter_f32(
+                target,
+                crate::context::TEXTURE_MAX_ANISOTROPY_EXT,
+                (sampler.anisotropy.max(1) as f32).min(max_anisotropy),
+            );
+        }
+        if !context.version().is_embedded {
+            context.tex_parameter_f32(target, crate::context::TEXTURE_LOD_BIAS, sampler.lod_bias);
+        }
+        context.tex_parameter_f32(target, crate::context::TEXTURE_MIN_LOD, sampler.min_lod);
+        context.tex_parameter_f32(target, crate::context::TEXTURE_MAX_LOD, sampler.max_lod);
+        if let Some(compare_function) = sampler.compare_function {
+            context.tex_parameter_i32(
+                target,
+                crate::context::TEXTURE_COMPARE_MODE,
+                crate::context::COMPARE_REF_TO_TEXTURE as i32,
+            );
+            context.tex_parameter_i32(
+                target,
+                crate::context::TEXTURE_COMPARE_FUNC,
+                compare_function_from(compare_function),
+            );
+        } else {
+            context.tex_parameter_i32(
+                target,
+                crate::context::TEXTURE_COMPARE_MODE,
+                crate::context::NONE as i32,
+            );
         }
     }
     context.error_check()
 }
 
+fn min_filter_from(min_filter: Interpolation, mip_map_filter: Option<Interpolation>) -> i32 {
+    (match (min_filter, mip_map_filter) {
+        (_, None) => return interpolation_from(min_filter),
+        (Interpolation::Nearest, Some(Interpolation::Nearest)) => {
+            crate::context::NEAREST_MIPMAP_NEAREST
+        }
+        (Interpolation::Linear, Some(Interpolation::Nearest)) => {
+            crate::context::LINEAR_MIPMAP_NEAREST
+        }
+        (Interpolation::Nearest, Some(Interpolation::Linear)) => {
+            crate::context::NEAREST_MIPMAP_LINEAR
+        }
+        (Interpolation::Linear, Some(Interpolation::Linear)) => {
+            crate::context::LINEAR_MIPMAP_LINEAR
+        }
+    }) as i32
+}
+
+fn compare_function_from(compare_function: DepthTest) -> i32 {
+    (match compare_function {
+        DepthTest::Never => crate::context::NEVER,
+        DepthTest::Less => crate::context::LESS,
+        DepthTest::Equal => crate::context::EQUAL,
+        DepthTest::LessOrEqual => crate::context::LEQUAL,
+        DepthTest::Greater => crate::context::GREATER,
+        DepthTest::NotEqual => crate::context::NOTEQUAL,
+        DepthTest::GreaterOrEqual => crate::context::GEQUAL,
+        DepthTest::Always => crate::context::ALWAYS,
+    }) as i32
+}
+
+fn srgba_data(cpu_texture: &CpuTexture) -> ThreeDResult<Vec<[u8; 4]>> {
+    Ok(match cpu_texture.data {
+        TextureData::RgbU8(ref data) => data.iter().map(|c| [c[0], c[1], c[2], 255]).collect(),
+        TextureData::RgbaU8(ref data) => data.clone(),
+        _ => Err(CoreError::InvalidSrgbTextureDataType)?,
+    })
+}
+
+fn check_sampler(sampler: &Sampler, is_integer: bool, is_depth: bool) -> ThreeDResult<()> {
+    if is_integer
+        && (sampler.min_filter != Interpolation::Nearest
+            || sampler.mag_filter != Interpolation::Nearest
+            || sampler.mip_map_filter.is_some())
+    {
+        Err(CoreError::InvalidIntegerTextureInterpolation)?;
+    }
+    if !is_depth && sampler.compare_function.is_some() {
+        Err(CoreError::InvalidSamplerCompareFunction)?;
+    }
+    if sampler.min_lod > sampler.max_lod {
+        Err(CoreError::MinimumLargerThanMaximum)?;
+    }
+    Ok(())
+}
+
+fn is_integer_format(internal_format: u32) -> bool {
+    matches!(
+        internal_format,
+        crate::context::R8I
+            | crate::context::R8UI
+            | crate::context::R16I
+            | crate::context::R16UI
+            | crate::context::R32I
+            | crate::context::R32UI
+            | crate::context::RG8I
+            | crate::context::RG8UI
+            | crate::context::RG16I
+            | crate::context::RG16UI
+            | crate::context::RG32I
+            | crate::context::RG32UI
+            | crate::context::RGB8I
+            | crate::context::RGB8UI
+            | crate::context::RGB16I
+            | crate::context::RGB16UI
+            | crate::context::RGB32I
+            | crate::context::RGB32UI
+            | crate::context::RGBA8I
+            | crate::context::RGBA8UI
+            | crate::context::RGBA16I
+            | crate::context::RGBA16UI
+            | crate::context::RGBA32I
+            | crate::context::RGBA32UI
+    )
+}
+
 fn calculate_number_of_mip_maps(
     mip_map_filter: Option<Interpolation>,
     width: u32,
@@ -151,11 +338,53 @@ fn calculate_number_of_mip_maps(
     }
 }
 
+///
+/// Estimates the GPU memory used by a texture with the given size, number of layers (for example 6 for a cube map)
+/// and number of mip maps, where the depth is reduced for each mip level for 3D textures.
+///
+fn texture_byte_size(
+    width: u32,
+    height: u32,
+    depth: Option<u32>,
+    layers: u32,
+    number_of_mip_maps: u32,
+    texel_byte_size: usize,
+) -> usize {
+    (0..number_of_mip_maps.max(1))
+        .map(|level| {
+            (width >> level).max(1) as usize
+                * (height >> level).max(1) as usize
+                * depth.map(|d| (d >> level).max(1)).unwrap_or(1) as usize
+        })
+        .sum::<usize>()
+        * layers as usize
+        * texel_byte_size
+}
+
+fn texel_byte_size_from_depth(format: DepthFormat) -> usize {
+    match format {
+        DepthFormat::Depth16 => 2,
+        DepthFormat::Depth24 | DepthFormat::Depth32F | DepthFormat::Depth24Stencil8 => 4,
+        DepthFormat::Depth32FStencil8 => 8,
+    }
+}
+
 fn internal_format_from_depth(format: DepthFormat) -> u32 {
     match format {
         DepthFormat::Depth16 => crate::context::DEPTH_COMPONENT16,
         DepthFormat::Depth24 => crate::context::DEPTH_COMPONENT24,
         DepthFormat::Depth32F => crate::context::DEPTH_COMPONENT32F,
+        DepthFormat::Depth24Stencil8 => crate::context::DEPTH24_STENCIL8,
+        DepthFormat::Depth32FStencil8 => crate::context::DEPTH32F_STENCIL8,
+    }
+}
+
+fn attachment_from_depth(format: DepthFormat) -> u32 {
+    match format {
+        DepthFormat::Depth24Stencil8 | DepthFormat::Depth32FStencil8 => {
+            crate::context::DEPTH_STENCIL_ATTACHMENT
+        }
+        _ => crate::context::DEPTH_ATTACHMENT,
     }
 }
 
@@ -191,3 +420,99 @@ fn check_data_length<T: TextureDataType>(
     }
     Ok(())
 }
+
+fn check_region(scissor_box: ScissorBox, width: u32, height: u32) -> ThreeDResult<()> {
+    let inside = |offset: i32, size: u32, max: u32| {
+        offset >= 0
+            && (offset as u32)
+                .checked_add(size)
+                .map(|end| end <= max)
+                .unwrap_or(false)
+    };
+    if !inside(scissor_box.x, scissor_box.width, width)
+        || !inside(scissor_box.y, scissor_box.height, height)
+    {
+        Err(CoreError::InvalidTextureRegion(scissor_box, width, height))?;
+    }
+    Ok(())
+}
+
+fn texture_data_from_color_targets(
+    color_targets: &[ColorTarget],
+    internal_format: u32,
+    flip: bool,
+) -> ThreeDResult<TextureData> {
+    Ok(match internal_format {
+        crate::context::R8
+        | crate::context::RG8
+        | crate::context::RGB8
+        | crate::context::RGBA8
+        | crate::context::SRGB8_ALPHA8 => {
+            let pixels = read_color_targets::<[u8; 4]>(color_targets, flip)?;
+            match internal_format {
+                crate::context::R8 => TextureData::RU8(pixels.iter().map(|p| p[0]).collect()),
+                crate::context::RG8 => {
+                    TextureData::RgU8(pixels.iter().map(|p| [p[0], p[1]]).collect())
+                }
+                crate::context::RGB8 => {
+                    TextureData::RgbU8(pixels.iter().map(|p| [p[0], p[1], p[2]]).collect())
+                }
+                _ => TextureData::RgbaU8(pixels),
+            }
+        }
+        crate::context::R16F
+        | crate::context::RG16F
+        | crate::context::RGB16F
+        | crate::context::RGBA16F
+        | crate::context::R32F
+        | crate::context::RG32F
+        | crate::context::RGB32F
+        | crate::context::RGBA32F => {
+            let pixels = read_color_targets::<[f32; 4]>(color_targets, flip)?;
+            let h = f16::from_f32;
+            match internal_format {
+                crate::context::R16F => TextureData::RF16(pixels.iter().map(|p| h(p[0])).collect()),
+                crate::context::RG16F => {
+                    TextureData::RgF16(pixels.iter().map(|p| [h(p[0]), h(p[1])]).collect())
+                }
+                crate::context::RGB16F => TextureData::RgbF16(
+                    pixels.iter().map(|p| [h(p[0]), h(p[1]), h(p[2])]).collect(),
+                ),
+                crate::context::RGBA16F => TextureData::RgbaF16(
+                    pixels
+                        .iter()
+                        .map(|p| [h(p[0]), h(p[1]), h(p[2]), h(p[3])])
+                        .collect(),
+                ),
+                crate::context::R32F => TextureData::RF32(pixels.iter().map(|p| p[0]).collect()),
+                crate::context::RG32F => {
+                    TextureData::RgF32(pixels.iter().map(|p| [p[0], p[1]]).collect())
+                }
+                crate::context::RGB32F => {
+                    TextureData::RgbF32(pixels.iter().map(|p| [p[0], p[1], p[2]]).collect())
+                }
+                _ => TextureData::RgbaF32(pixels),
+            }
+        }
+        _ => Err(CoreError::IntegerTextureToCpuTexture)?,
+    })
+}
+
+fn read_color_targets<T: TextureDataType>(
+    color_targets: &[ColorTarget],
+    flip: bool,
+) -> ThreeDResult<Vec<T>> {
+    let mut pixels = Vec::new();
+    for color_target in color_targets {
+        let mut p = color_target.read::<T>()?;
+        if flip {
+            flip_y(
+                &mut p,
+                color_target.width() as usize,
+                color_target.height() as usize,
+            );
+        }
+        pixels.extend(p);
+    }
+    Ok(pixels)
+}
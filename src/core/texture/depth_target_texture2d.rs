@@ -1,8 +1,9 @@
 use crate::core::texture::*;
 
 ///
-/// Type of formats for depth render targets ([DepthTargetTexture2D] and
-/// [DepthTargetTexture2DArray]).
+/// Type of formats for depth render targets ([DepthTargetTexture2D],
+/// [DepthTargetTexture2DArray], [DepthTargetTextureCubeMap] and [DepthTargetTexture2DMultisample]).
+/// The formats with a stencil component are needed to be able to use the [StencilTest].
 ///
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub enum DepthFormat {
@@ -12,6 +13,10 @@ pub enum DepthFormat {
     Depth24,
     /// 32 bit per pixel.
     Depth32F,
+    /// 24 bit depth and 8 bit stencil per pixel.
+    Depth24Stencil8,
+    /// 32 bit depth and 8 bit stencil per pixel.
+    Depth32FStencil8,
 }
 
 ///
@@ -22,6 +27,9 @@ pub struct DepthTargetTexture2D {
     id: crate::context::Texture,
     width: u32,
     height: u32,
+    format: DepthFormat,
+    sampler: Sampler,
+    resource: ResourceHandle,
 }
 
 impl DepthTargetTexture2D {
@@ -42,18 +50,29 @@ impl DepthTargetTexture2D {
             id,
             width,
             height,
+            format,
+            sampler: Sampler {
+                min_filter: Interpolation::Nearest,
+                mag_filter: Interpolation::Nearest,
+                mip_map_filter: None,
+                wrap_s,
+                wrap_t,
+                ..Default::default()
+            },
+            resource: context.track_resource(
+                ResourceKind::DepthTexture,
+                texture_byte_size(
+                    width,
+                    height,
+                    None,
+                    1,
+                    1,
+                    texel_byte_size_from_depth(format),
+                ),
+            ),
         };
         texture.bind();
-        set_parameters(
-            context,
-            crate::context::TEXTURE_2D,
-            Interpolation::Nearest,
-            Interpolation::Nearest,
-            None,
-            wrap_s,
-            wrap_t,
-            None,
-        )?;
+        set_parameters(context, crate::context::TEXTURE_2D, &texture.sampler, 1)?;
         unsafe {
             context.tex_storage_2d(
                 crate::context::TEXTURE_2D,
@@ -94,6 +113,45 @@ impl DepthTargetTexture2D {
         Ok(())
     }
 
+    ///
+    /// Reads the depth values of this texture back from the GPU and returns them as a [CpuTexture] with one 32 bit float per pixel.
+    /// The data is returned row by row starting from the top, in the same way as for [Texture2D::fill].
+    /// The filtering and wrapping parameters of the returned cpu texture are set to default values.
+    ///
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn to_cpu_texture(&self) -> ThreeDResult<CpuTexture> {
+        let mut pixels = DepthTarget::new_texture2d(&self.context, self).read()?;
+        flip_y(&mut pixels, self.width as usize, self.height as usize);
+        Ok(CpuTexture {
+            data: TextureData::RF32(pixels),
+            width: self.width,
+            height: self.height,
+            ..Default::default()
+        })
+    }
+
+    ///
+    /// Returns the [Sampler] which describes how this texture is sampled in a shader.
+    ///
+    pub fn sampler(&self) -> Sampler {
+        self.sampler
+    }
+
+    ///
+    /// Changes how this texture is sampled in a shader, see [Sampler].
+    /// For example, use a [Sampler::compare_function] together with linear interpolation to get hardware filtered shadow maps.
+    ///
+    /// # Errors
+    /// Returns an error if the minimum level of detail of the sampler is larger than the maximum level of detail.
+    ///
+    pub fn set_sampler(&mut self, sampler: Sampler) -> ThreeDResult<()> {
+        check_sampler(&sampler, false, true)?;
+        self.bind();
+        set_parameters(&self.context, crate::context::TEXTURE_2D, &sampler, 1)?;
+        self.sampler = sampler;
+        Ok(())
+    }
+
     /// The width of this texture.
     pub fn width(&self) -> u32 {
         self.width
@@ -104,11 +162,32 @@ impl DepthTargetTexture2D {
         self.height
     }
 
+    ///
+    /// Sets a debug label for this texture which is used to identify it in [Context::resource_stats]
+    /// and in GPU debuggers if debug output is supported, see [Context::supports_debug_output].
+    ///
+    pub fn set_label(&self, label: &str) {
+        self.resource.set_label(label);
+        self.context.label_object(
+            label,
+            crate::context::TEXTURE,
+            crate::context::TEXTURE_BINDING_2D,
+            || self.bind(),
+        );
+    }
+
+    ///
+    /// Returns the debug label of this texture, see [DepthTargetTexture2D::set_label].
+    ///
+    pub fn label(&self) -> Option<String> {
+        self.resource.label()
+    }
+
     pub(in crate::core) fn bind_as_depth_target(&self) {
         unsafe {
             self.context.framebuffer_texture_2d(
                 crate::context::FRAMEBUFFER,
-                crate::context::DEPTH_ATTACHMENT,
+                attachment_from_depth(self.format),
                 crate::context::TEXTURE_2D,
                 Some(self.id),
                 0,
@@ -117,15 +196,14 @@ impl DepthTargetTexture2D {
     }
 
     pub(in crate::core) fn bind(&self) {
-        unsafe {
-            self.context
-                .bind_texture(crate::context::TEXTURE_2D, Some(self.id));
-        }
+        self.context
+            .bind_texture_cached(crate::context::TEXTURE_2D, self.id);
     }
 }
 
 impl Drop for DepthTargetTexture2D {
     fn drop(&mut self) {
+        self.context.forget_texture(self.id);
         unsafe {
             self.context.delete_texture(self.id);
         }
@@ -9,6 +9,9 @@ pub struct DepthTargetTexture2DArray {
     width: u32,
     height: u32,
     depth: u32,
+    format: DepthFormat,
+    sampler: Sampler,
+    resource: ResourceHandle,
 }
 
 impl DepthTargetTexture2DArray {
@@ -31,17 +34,33 @@ impl DepthTargetTexture2DArray {
             width,
             height,
             depth,
+            format,
+            sampler: Sampler {
+                min_filter: Interpolation::Nearest,
+                mag_filter: Interpolation::Nearest,
+                mip_map_filter: None,
+                wrap_s,
+                wrap_t,
+                ..Default::default()
+            },
+            resource: context.track_resource(
+                ResourceKind::DepthTexture,
+                texture_byte_size(
+                    width,
+                    height,
+                    None,
+                    depth,
+                    1,
+                    texel_byte_size_from_depth(format),
+                ),
+            ),
         };
         texture.bind();
         set_parameters(
             context,
             crate::context::TEXTURE_2D_ARRAY,
-            Interpolation::Nearest,
-            Interpolation::Nearest,
-            None,
-            wrap_s,
-            wrap_t,
-            None,
+            &texture.sampler,
+            1,
         )?;
         unsafe {
             context.tex_storage_3d(
@@ -85,6 +104,28 @@ impl DepthTargetTexture2DArray {
         Ok(())
     }
 
+    ///
+    /// Returns the [Sampler] which describes how this texture is sampled in a shader.
+    ///
+    pub fn sampler(&self) -> Sampler {
+        self.sampler
+    }
+
+    ///
+    /// Changes how this texture is sampled in a shader, see [Sampler].
+    /// For example, use a [Sampler::compare_function] together with linear interpolation to get hardware filtered shadow maps.
+    ///
+    /// # Errors
+    /// Returns an error if the minimum level of detail of the sampler is larger than the maximum level of detail.
+    ///
+    pub fn set_sampler(&mut self, sampler: Sampler) -> ThreeDResult<()> {
+        check_sampler(&sampler, false, true)?;
+        self.bind();
+        set_parameters(&self.context, crate::context::TEXTURE_2D_ARRAY, &sampler, 1)?;
+        self.sampler = sampler;
+        Ok(())
+    }
+
     /// The width of this texture.
     pub fn width(&self) -> u32 {
         self.width
@@ -100,11 +141,32 @@ impl DepthTargetTexture2DArray {
         self.depth
     }
 
+    ///
+    /// Sets a debug label for this texture array which is used to identify it in [Context::resource_stats]
+    /// and in GPU debuggers if debug output is supported, see [Context::supports_debug_output].
+    ///
+    pub fn set_label(&self, label: &str) {
+        self.resource.set_label(label);
+        self.context.label_object(
+            label,
+            crate::context::TEXTURE,
+            crate::context::TEXTURE_BINDING_2D_ARRAY,
+            || self.bind(),
+        );
+    }
+
+    ///
+    /// Returns the debug label of this texture array, see [DepthTargetTexture2DArray::set_label].
+    ///
+    pub fn label(&self) -> Option<String> {
+        self.resource.label()
+    }
+
     pub(in crate::core) fn bind_as_depth_target(&self, layer: u32) {
         unsafe {
             self.context.framebuffer_texture_layer(
                 crate::context::DRAW_FRAMEBUFFER,
-                crate::context::DEPTH_ATTACHMENT,
+                attachment_from_depth(self.format),
                 Some(self.id),
                 0,
                 layer as i32,
@@ -113,15 +175,14 @@ impl DepthTargetTexture2DArray {
     }
 
     pub(in crate::core) fn bind(&self) {
-        unsafe {
-            self.context
-                .bind_texture(crate::context::TEXTURE_2D_ARRAY, Some(self.id));
-        }
+        self.context
+            .bind_texture_cached(crate::context::TEXTURE_2D_ARRAY, self.id);
     }
 }
 
 impl Drop for DepthTargetTexture2DArray {
     fn drop(&mut self) {
+        self.context.forget_texture(self.id);
         unsafe {
             self.context.delete_texture(self.id);
         }
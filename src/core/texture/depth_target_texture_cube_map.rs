@@ -8,6 +8,9 @@ pub struct DepthTargetTextureCubeMap {
     id: crate::context::Texture,
     width: u32,
     height: u32,
+    format: DepthFormat,
+    sampler: Sampler,
+    resource: ResourceHandle,
 }
 
 impl DepthTargetTextureCubeMap {
@@ -29,17 +32,34 @@ impl DepthTargetTextureCubeMap {
             id,
             width,
             height,
+            format,
+            sampler: Sampler {
+                min_filter: Interpolation::Nearest,
+                mag_filter: Interpolation::Nearest,
+                mip_map_filter: None,
+                wrap_s,
+                wrap_t,
+                wrap_r,
+                ..Default::default()
+            },
+            resource: context.track_resource(
+                ResourceKind::DepthTexture,
+                texture_byte_size(
+                    width,
+                    height,
+                    None,
+                    6,
+                    1,
+                    texel_byte_size_from_depth(format),
+                ),
+            ),
         };
         texture.bind();
         set_parameters(
             context,
             crate::context::TEXTURE_CUBE_MAP,
-            Interpolation::Nearest,
-            Interpolation::Nearest,
-            None,
-            wrap_s,
-            wrap_t,
-            Some(wrap_r),
+            &texture.sampler,
+            1,
         )?;
         unsafe {
             context.tex_storage_2d(
@@ -82,6 +102,28 @@ impl DepthTargetTextureCubeMap {
         Ok(())
     }
 
+    ///
+    /// Returns the [Sampler] which describes how this texture is sampled in a shader.
+    ///
+    pub fn sampler(&self) -> Sampler {
+        self.sampler
+    }
+
+    ///
+    /// Changes how this texture is sampled in a shader, see [Sampler].
+    /// For example, use a [Sampler::compare_function] together with linear interpolation to get hardware filtered shadow maps.
+    ///
+    /// # Errors
+    /// Returns an error if the minimum level of detail of the sampler is larger than the maximum level of detail.
+    ///
+    pub fn set_sampler(&mut self, sampler: Sampler) -> ThreeDResult<()> {
+        check_sampler(&sampler, false, true)?;
+        self.bind();
+        set_parameters(&self.context, crate::context::TEXTURE_CUBE_MAP, &sampler, 1)?;
+        self.sampler = sampler;
+        Ok(())
+    }
+
     /// The width of this texture.
     pub fn width(&self) -> u32 {
         self.width
@@ -92,11 +134,32 @@ impl DepthTargetTextureCubeMap {
         self.height
     }
 
+    ///
+    /// Sets a debug label for this texture which is used to identify it in [Context::resource_stats]
+    /// and in GPU debuggers if debug output is supported, see [Context::supports_debug_output].
+    ///
+    pub fn set_label(&self, label: &str) {
+        self.resource.set_label(label);
+        self.context.label_object(
+            label,
+            crate::context::TEXTURE,
+            crate::context::TEXTURE_BINDING_CUBE_MAP,
+            || self.bind(),
+        );
+    }
+
+    ///
+    /// Returns the debug label of this texture, see [DepthTargetTextureCubeMap::set_label].
+    ///
+    pub fn label(&self) -> Option<String> {
+        self.resource.label()
+    }
+
     pub(in crate::core) fn bind_as_depth_target(&self, side: CubeMapSide) {
         unsafe {
             self.context.framebuffer_texture_2d(
                 crate::context::DRAW_FRAMEBUFFER,
-                crate::context::DEPTH_ATTACHMENT,
+                attachment_from_depth(self.format),
                 side.to_const(),
                 Some(self.id),
                 0,
@@ -105,15 +168,14 @@ impl DepthTargetTextureCubeMap {
     }
 
     pub(in crate::core) fn bind(&self) {
-        unsafe {
-            self.context
-                .bind_texture(crate::context::TEXTURE_CUBE_MAP, Some(self.id));
-        }
+        self.context
+            .bind_texture_cached(crate::context::TEXTURE_CUBE_MAP, self.id);
     }
 }
 
 impl Drop for DepthTargetTextureCubeMap {
     fn drop(&mut self) {
+        self.context.forget_texture(self.id);
         unsafe {
             self.context.delete_texture(self.id);
         }
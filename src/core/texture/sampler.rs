@@ -0,0 +1,61 @@
+use crate::core::texture::*;
+
+///
+/// Describes how a texture is sampled in a shader, ie. the filtering, wrapping, level of detail and depth comparison.
+/// The filtering and wrapping is specified when constructing a texture, the rest is set to default values.
+/// The sampler can afterwards be changed, see for example [Texture2D::set_sampler].
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sampler {
+    /// The interpolation used when the texture is minified, ie. when one pixel covers several texels.
+    pub min_filter: Interpolation,
+    /// The interpolation used when the texture is magnified, ie. when one texel covers several pixels.
+    pub mag_filter: Interpolation,
+    /// The interpolation between mip map levels. Has no effect if the texture was constructed without mip maps.
+    pub mip_map_filter: Option<Interpolation>,
+    /// The wrapping in the s-direction (the first texture coordinate).
+    pub wrap_s: Wrapping,
+    /// The wrapping in the t-direction (the second texture coordinate).
+    pub wrap_t: Wrapping,
+    /// The wrapping in the r-direction (the third texture coordinate). Only used by 3D and cube map textures.
+    pub wrap_r: Wrapping,
+    ///
+    /// The maximum number of samples used for anisotropic filtering, which improves the quality of textures viewed at grazing angles.
+    /// A value of 1 disables anisotropic filtering, a value of 16 is usually the maximum supported.
+    /// The value is clamped to the maximum supported by the graphics driver and ignored if anisotropic filtering is not supported.
+    ///
+    pub anisotropy: u32,
+    ///
+    /// A bias added to the level of detail which is used to select the mip map level, a positive value makes the texture more blurry and a negative value makes it more sharp.
+    /// **Note:** Not supported on web and OpenGL ES, in which case this value is ignored.
+    ///
+    pub lod_bias: f32,
+    /// The minimum level of detail, ie. the lowest mip map level that can be selected.
+    pub min_lod: f32,
+    /// The maximum level of detail, ie. the highest mip map level that can be selected.
+    pub max_lod: f32,
+    ///
+    /// If specified, sampling the texture returns the result of comparing the given reference value with the stored depth value using this function,
+    /// instead of the depth value itself. This is typically used for shadow maps and requires a shadow sampler in the shader, for example `sampler2DShadow`.
+    /// Can only be used for depth textures.
+    ///
+    pub compare_function: Option<DepthTest>,
+}
+
+impl Default for Sampler {
+    fn default() -> Self {
+        Self {
+            min_filter: Interpolation::Linear,
+            mag_filter: Interpolation::Linear,
+            mip_map_filter: Some(Interpolation::Linear),
+            wrap_s: Wrapping::Repeat,
+            wrap_t: Wrapping::Repeat,
+            wrap_r: Wrapping::Repeat,
+            anisotropy: 1,
+            lod_bias: 0.0,
+            min_lod: -1000.0,
+            max_lod: 1000.0,
+            compare_function: None,
+        }
+    }
+}
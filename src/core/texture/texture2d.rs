@@ -9,7 +9,10 @@ pub struct Texture2D {
     width: u32,
     height: u32,
     number_of_mip_maps: u32,
+    sampler: Sampler,
     data_byte_size: usize,
+    internal_format: u32,
+    resource: ResourceHandle,
 }
 
 impl Texture2D {
@@ -67,6 +70,100 @@ impl Texture2D {
         wrap_s: Wrapping,
         wrap_t: Wrapping,
     ) -> ThreeDResult<Self> {
+        Self::new_empty_with_internal_format::<T>(
+            context,
+            width,
+            height,
+            min_filter,
+            mag_filter,
+            mip_map_filter,
+            wrap_s,
+            wrap_t,
+            T::internal_format(),
+        )
+    }
+
+    ///
+    /// Constructs a new texture with the given data which is assumed to be in sRGB color space.
+    /// The data is stored in sRGB color space on the GPU and is automatically converted to linear color space when sampled in a shader,
+    /// which means that filtering and mip mapping is done correctly in linear color space.
+    ///
+    /// **Note:** RGB data is stored with an alpha channel, since mip maps cannot be generated for sRGB textures without alpha on all platforms,
+    /// so the texture uses a third more memory than an RGB texture.
+    ///
+    /// # Errors
+    /// Returns an error if the data of the cpu texture is not RGB or RGBA bytes.
+    ///
+    pub fn new_srgb(context: &Context, cpu_texture: &CpuTexture) -> ThreeDResult<Self> {
+        let data = srgba_data(cpu_texture)?;
+        let mut texture = Self::new_empty_with_internal_format::<[u8; 4]>(
+            context,
+            cpu_texture.width,
+            cpu_texture.height,
+            cpu_texture.min_filter,
+            cpu_texture.mag_filter,
+            cpu_texture.mip_map_filter,
+            cpu_texture.wrap_s,
+            cpu_texture.wrap_t,
+            crate::context::SRGB8_ALPHA8,
+        )?;
+        texture.fill(&data)?;
+        Ok(texture)
+    }
+
+    ///
+    /// Constructs a new empty 2D texture which stores RGBA bytes in sRGB color space.
+    /// The values are automatically converted to linear color space when sampled in a shader
+    /// and, when used as a render target, the values written to it are expected to be in linear color space and are automatically converted to sRGB.
+    /// Use [Texture2D::fill] with `[u8; 4]` data to fill it with sRGB data.
+    ///
+    /// **Note:** The built-in materials output sRGB values and should therefore only be used to render into textures with a linear format.
+    ///
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_empty_srgb(
+        context: &Context,
+        width: u32,
+        height: u32,
+        min_filter: Interpolation,
+        mag_filter: Interpolation,
+        mip_map_filter: Option<Interpolation>,
+        wrap_s: Wrapping,
+        wrap_t: Wrapping,
+    ) -> ThreeDResult<Self> {
+        Self::new_empty_with_internal_format::<[u8; 4]>(
+            context,
+            width,
+            height,
+            min_filter,
+            mag_filter,
+            mip_map_filter,
+            wrap_s,
+            wrap_t,
+            crate::context::SRGB8_ALPHA8,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn new_empty_with_internal_format<T: TextureDataType>(
+        context: &Context,
+        width: u32,
+        height: u32,
+        min_filter: Interpolation,
+        mag_filter: Interpolation,
+        mip_map_filter: Option<Interpolation>,
+        wrap_s: Wrapping,
+        wrap_t: Wrapping,
+        internal_format: u32,
+    ) -> ThreeDResult<Self> {
+        let sampler = Sampler {
+            min_filter,
+            mag_filter,
+            mip_map_filter,
+            wrap_s,
+            wrap_t,
+            ..Default::default()
+        };
+        check_sampler(&sampler, is_integer_data_type::<T>(), false)?;
         let id = generate(context)?;
         let number_of_mip_maps = calculate_number_of_mip_maps(mip_map_filter, width, height, None);
         let texture = Self {
@@ -75,28 +172,33 @@ impl Texture2D {
             width,
             height,
             number_of_mip_maps,
+            sampler,
             data_byte_size: std::mem::size_of::<T>(),
+            internal_format,
+            resource: context.track_resource(
+                ResourceKind::Texture2D,
+                texture_byte_size(
+                    width,
+                    height,
+                    None,
+                    1,
+                    number_of_mip_maps,
+                    std::mem::size_of::<T>(),
+                ),
+            ),
         };
         texture.bind();
         set_parameters(
             context,
             crate::context::TEXTURE_2D,
-            min_filter,
-            mag_filter,
-            if number_of_mip_maps == 1 {
-                None
-            } else {
-                mip_map_filter
-            },
-            wrap_s,
-            wrap_t,
-            None,
+            &texture.sampler,
+            number_of_mip_maps,
         )?;
         unsafe {
             context.tex_storage_2d(
                 crate::context::TEXTURE_2D,
                 number_of_mip_maps as i32,
-                T::internal_format(),
+                internal_format,
                 width as i32,
                 height as i32,
             );
@@ -135,6 +237,52 @@ impl Texture2D {
         self.context.error_check()
     }
 
+    ///
+    /// Fills the part of this texture that is inside the given scissor box with the given data.
+    /// The scissor box is defined in pixels from the bottom left corner of the texture
+    /// while the data is given row by row starting from the top, in the same way as for [Texture2D::fill].
+    /// Useful for updating only a part of the texture, for example when streaming tiles or video frames.
+    ///
+    /// # Errors
+    /// Returns an error if the scissor box is not inside the texture or if the length of the data does not correspond to the size of the scissor box and the format specified at construction.
+    ///
+    pub fn fill_partially<T: TextureDataType>(
+        &mut self,
+        scissor_box: ScissorBox,
+        data: &[T],
+    ) -> ThreeDResult<()> {
+        check_region(scissor_box, self.width, self.height)?;
+        check_data_length(
+            scissor_box.width,
+            scissor_box.height,
+            1,
+            self.data_byte_size,
+            data,
+        )?;
+        self.bind();
+        let mut data = data.to_owned();
+        flip_y(
+            &mut data,
+            scissor_box.width as usize,
+            scissor_box.height as usize,
+        );
+        unsafe {
+            self.context.tex_sub_image_2d(
+                crate::context::TEXTURE_2D,
+                0,
+                scissor_box.x,
+                scissor_box.y,
+                scissor_box.width as i32,
+                scissor_box.height as i32,
+                format_from_data_type::<T>(),
+                T::data_type(),
+                crate::context::PixelUnpackData::Slice(to_byte_slice(&data)),
+            );
+        }
+        self.generate_mip_maps();
+        self.context.error_check()
+    }
+
     ///
     /// Returns a [ColorTarget] which can be used to clear, write to and read from the given mip level of this texture.
     /// Combine this together with a [DepthTarget] with [RenderTarget::new] to be able to write to both a depth and color target at the same time.
@@ -178,6 +326,53 @@ impl Texture2D {
         self.as_color_target(None).read_partially(viewport.into())
     }
 
+    ///
+    /// Reads the given mip level of this texture back from the GPU and returns it as a [CpuTexture].
+    /// The data is returned in the same format as the texture, row by row starting from the top, in the same way as for [Texture2D::fill].
+    /// The filtering and wrapping parameters of the returned cpu texture are set to default values.
+    ///
+    pub fn to_cpu_texture(&self, mip_level: u32) -> ThreeDResult<CpuTexture> {
+        let color_target = ColorTarget::new_texture2d(&self.context, self, Some(mip_level));
+        Ok(CpuTexture {
+            data: texture_data_from_color_targets(
+                std::slice::from_ref(&color_target),
+                self.internal_format,
+                false,
+            )?,
+            width: color_target.width(),
+            height: color_target.height(),
+            ..Default::default()
+        })
+    }
+
+    ///
+    /// Returns the [Sampler] which describes how this texture is sampled in a shader.
+    ///
+    pub fn sampler(&self) -> Sampler {
+        self.sampler
+    }
+
+    ///
+    /// Changes how this texture is sampled in a shader, see [Sampler].
+    /// For example, set [Sampler::anisotropy] to improve the quality when the texture is viewed at grazing angles.
+    ///
+    /// # Errors
+    /// Returns an error if the minimum level of detail of the sampler is larger than the maximum level of detail, if the sampler uses a depth comparison function
+    /// or if this is an integer texture and the sampler uses linear interpolation or mip mapping.
+    ///
+    pub fn set_sampler(&mut self, sampler: Sampler) -> ThreeDResult<()> {
+        check_sampler(&sampler, is_integer_format(self.internal_format), false)?;
+        self.bind();
+        set_parameters(
+            &self.context,
+            crate::context::TEXTURE_2D,
+            &sampler,
+            self.number_of_mip_maps,
+        )?;
+        self.sampler = sampler;
+        Ok(())
+    }
+
     /// The width of this texture.
     pub fn width(&self) -> u32 {
         self.width
@@ -188,6 +383,11 @@ impl Texture2D {
         self.height
     }
 
+    /// Whether this texture stores the data in sRGB color space, see [Texture2D::new_srgb].
+    pub fn is_srgb(&self) -> bool {
+        self.internal_format == crate::context::SRGB8_ALPHA8
+    }
+
     pub(crate) fn generate_mip_maps(&self) {
         if self.number_of_mip_maps > 1 {
             self.bind();
@@ -197,6 +397,27 @@ impl Texture2D {
         }
     }
 
+    ///
+    /// Sets a debug label for this texture which is used to identify it in [Context::resource_stats]
+    /// and in GPU debuggers if debug output is supported, see [Context::supports_debug_output].
+    ///
+    pub fn set_label(&self, label: &str) {
+        self.resource.set_label(label);
+        self.context.label_object(
+            label,
+            crate::context::TEXTURE,
+            crate::context::TEXTURE_BINDING_2D,
+            || self.bind(),
+        );
+    }
+
+    ///
+    /// Returns the debug label of this texture, see [Texture2D::set_label].
+    ///
+    pub fn label(&self) -> Option<String> {
+        self.resource.label()
+    }
+
     pub(in crate::core) fn bind_as_color_target(&self, channel: u32, mip_level: u32) {
         unsafe {
             self.context.framebuffer_texture_2d(
@@ -209,15 +430,14 @@ impl Texture2D {
         }
     }
     pub(in crate::core) fn bind(&self) {
-        unsafe {
-            self.context
-                .bind_texture(crate::context::TEXTURE_2D, Some(self.id));
-        }
+        self.context
+            .bind_texture_cached(crate::context::TEXTURE_2D, self.id);
     }
 }
 
 impl Drop for Texture2D {
     fn drop(&mut self) {
+        self.context.forget_texture(self.id);
         unsafe {
             self.context.delete_texture(self.id);
         }
@@ -13,11 +13,199 @@ pub struct Texture2DArray {
     height: u32,
     depth: u32,
     number_of_mip_maps: u32,
+    sampler: Sampler,
+    data_byte_size: usize,
+    internal_format: u32,
+    resource: ResourceHandle,
 }
 
 impl Texture2DArray {
     ///
-    /// Creates a new array of 2D textures.
+    /// Creates a new array of 2D textures from the given [CpuTexture]s, one for each layer.
+    /// All of the cpu textures must have the same size and contain data with the same [TextureDataType].
+    /// The parameters (filtering and wrapping) of the first cpu texture are used for the whole array.
+    ///
+    /// # Errors
+    /// Returns an error if no cpu textures are given or if the cpu textures do not have the same size and data type.
+    ///
+    pub fn new(context: &Context, cpu_textures: &[&CpuTexture]) -> ThreeDResult<Self> {
+        let cpu_texture = cpu_textures.first().ok_or(CoreError::EmptyTextureArray)?;
+        if cpu_textures
+            .iter()
+            .any(|t| std::mem::discriminant(&t.data) != std::mem::discriminant(&cpu_texture.data))
+        {
+            Err(CoreError::InvalidTextureArrayTextureDataType)?;
+        }
+        if let Some(t) = cpu_textures
+            .iter()
+            .find(|t| t.width != cpu_texture.width || t.height != cpu_texture.height)
+        {
+            Err(CoreError::InvalidTextureArrayTextureSize(
+                cpu_texture.width,
+                cpu_texture.height,
+                t.width,
+                t.height,
+            ))?;
+        }
+        let mut texture = match cpu_texture.data {
+            TextureData::RU8(_) => Self::new_empty::<u8>(
+                context,
+                cpu_texture.width,
+                cpu_texture.height,
+                cpu_textures.len() as u32,
+                cpu_texture.min_filter,
+                cpu_texture.mag_filter,
+                cpu_texture.mip_map_filter,
+                cpu_texture.wrap_s,
+                cpu_texture.wrap_t,
+            )?,
+            TextureData::RgU8(_) => Self::new_empty::<[u8; 2]>(
+                context,
+                cpu_texture.width,
+                cpu_texture.height,
+                cpu_textures.len() as u32,
+                cpu_texture.min_filter,
+                cpu_texture.mag_filter,
+                cpu_texture.mip_map_filter,
+                cpu_texture.wrap_s,
+                cpu_texture.wrap_t,
+            )?,
+            TextureData::RgbU8(_) => Self::new_empty::<[u8; 3]>(
+                context,
+                cpu_texture.width,
+                cpu_texture.height,
+                cpu_textures.len() as u32,
+                cpu_texture.min_filter,
+                cpu_texture.mag_filter,
+                cpu_texture.mip_map_filter,
+                cpu_texture.wrap_s,
+                cpu_texture.wrap_t,
+            )?,
+            TextureData::RgbaU8(_) => Self::new_empty::<[u8; 4]>(
+                context,
+                cpu_texture.width,
+                cpu_texture.height,
+                cpu_textures.len() as u32,
+                cpu_texture.min_filter,
+                cpu_texture.mag_filter,
+                cpu_texture.mip_map_filter,
+                cpu_texture.wrap_s,
+                cpu_texture.wrap_t,
+            )?,
+            TextureData::RF16(_) => Self::new_empty::<f16>(
+                context,
+                cpu_texture.width,
+                cpu_texture.height,
+                cpu_textures.len() as u32,
+                cpu_texture.min_filter,
+                cpu_texture.mag_filter,
+                cpu_texture.mip_map_filter,
+                cpu_texture.wrap_s,
+                cpu_texture.wrap_t,
+            )?,
+            TextureData::RgF16(_) => Self::new_empty::<[f16; 2]>(
+                context,
+                cpu_texture.width,
+                cpu_texture.height,
+                cpu_textures.len() as u32,
+                cpu_texture.min_filter,
+                cpu_texture.mag_filter,
+                cpu_texture.mip_map_filter,
+                cpu_texture.wrap_s,
+                cpu_texture.wrap_t,
+            )?,
+            TextureData::RgbF16(_) => Self::new_empty::<[f16; 3]>(
+                context,
+                cpu_texture.width,
+                cpu_texture.height,
+                cpu_textures.len() as u32,
+                cpu_texture.min_filter,
+                cpu_texture.mag_filter,
+                cpu_texture.mip_map_filter,
+                cpu_texture.wrap_s,
+                cpu_texture.wrap_t,
+            )?,
+            TextureData::RgbaF16(_) => Self::new_empty::<[f16; 4]>(
+                context,
+                cpu_texture.width,
+                cpu_texture.height,
+                cpu_textures.len() as u32,
+                cpu_texture.min_filter,
+                cpu_texture.mag_filter,
+                cpu_texture.mip_map_filter,
+                cpu_texture.wrap_s,
+                cpu_texture.wrap_t,
+            )?,
+            TextureData::RF32(_) => Self::new_empty::<f32>(
+                context,
+                cpu_texture.width,
+                cpu_texture.height,
+                cpu_textures.len() as u32,
+                cpu_texture.min_filter,
+                cpu_texture.mag_filter,
+                cpu_texture.mip_map_filter,
+                cpu_texture.wrap_s,
+                cpu_texture.wrap_t,
+            )?,
+            TextureData::RgF32(_) => Self::new_empty::<[f32; 2]>(
+                context,
+                cpu_texture.width,
+                cpu_texture.height,
+                cpu_textures.len() as u32,
+                cpu_texture.min_filter,
+                cpu_texture.mag_filter,
+                cpu_texture.mip_map_filter,
+                cpu_texture.wrap_s,
+                cpu_texture.wrap_t,
+            )?,
+            TextureData::RgbF32(_) => Self::new_empty::<[f32; 3]>(
+                context,
+                cpu_texture.width,
+                cpu_texture.height,
+                cpu_textures.len() as u32,
+                cpu_texture.min_filter,
+                cpu_texture.mag_filter,
+                cpu_texture.mip_map_filter,
+                cpu_texture.wrap_s,
+                cpu_texture.wrap_t,
+            )?,
+            TextureData::RgbaF32(_) => Self::new_empty::<[f32; 4]>(
+                context,
+                cpu_texture.width,
+                cpu_texture.height,
+                cpu_textures.len() as u32,
+                cpu_texture.min_filter,
+                cpu_texture.mag_filter,
+                cpu_texture.mip_map_filter,
+                cpu_texture.wrap_s,
+                cpu_texture.wrap_t,
+            )?,
+        };
+        for (layer, cpu_texture) in cpu_textures.iter().enumerate() {
+            texture.fill_with_cpu_texture(layer as u32, cpu_texture)?;
+        }
+        Ok(texture)
+    }
+
+    fn fill_with_cpu_texture(&mut self, layer: u32, cpu_texture: &CpuTexture) -> ThreeDResult<()> {
+        match cpu_texture.data {
+            TextureData::RU8(ref data) => self.fill(layer, data),
+            TextureData::RgU8(ref data) => self.fill(layer, data),
+            TextureData::RgbU8(ref data) => self.fill(layer, data),
+            TextureData::RgbaU8(ref data) => self.fill(layer, data),
+            TextureData::RF16(ref data) => self.fill(layer, data),
+            TextureData::RgF16(ref data) => self.fill(layer, data),
+            TextureData::RgbF16(ref data) => self.fill(layer, data),
+            TextureData::RgbaF16(ref data) => self.fill(layer, data),
+            TextureData::RF32(ref data) => self.fill(layer, data),
+            TextureData::RgF32(ref data) => self.fill(layer, data),
+            TextureData::RgbF32(ref data) => self.fill(layer, data),
+            TextureData::RgbaF32(ref data) => self.fill(layer, data),
+        }
+    }
+
+    ///
+    /// Creates a new empty array of 2D textures.
     ///
     pub fn new_empty<T: TextureDataType>(
         context: &Context,
@@ -30,6 +218,15 @@ impl Texture2DArray {
         wrap_s: Wrapping,
         wrap_t: Wrapping,
     ) -> ThreeDResult<Self> {
+        let sampler = Sampler {
+            min_filter,
+            mag_filter,
+            mip_map_filter,
+            wrap_s,
+            wrap_t,
+            ..Default::default()
+        };
+        check_sampler(&sampler, is_integer_data_type::<T>(), false)?;
         let id = generate(context)?;
         let number_of_mip_maps = calculate_number_of_mip_maps(mip_map_filter, width, height, None);
         let texture = Self {
@@ -39,21 +236,27 @@ impl Texture2DArray {
             height,
             depth,
             number_of_mip_maps,
+            sampler,
+            data_byte_size: std::mem::size_of::<T>(),
+            internal_format: T::internal_format(),
+            resource: context.track_resource(
+                ResourceKind::Texture2DArray,
+                texture_byte_size(
+                    width,
+                    height,
+                    None,
+                    depth,
+                    number_of_mip_maps,
+                    std::mem::size_of::<T>(),
+                ),
+            ),
         };
         texture.bind();
         set_parameters(
             context,
             crate::context::TEXTURE_2D_ARRAY,
-            min_filter,
-            mag_filter,
-            if number_of_mip_maps == 1 {
-                None
-            } else {
-                mip_map_filter
-            },
-            wrap_s,
-            wrap_t,
-            None,
+            &texture.sampler,
+            number_of_mip_maps,
         )?;
         unsafe {
             context.tex_storage_3d(
@@ -70,6 +273,75 @@ impl Texture2DArray {
         Ok(texture)
     }
 
+    ///
+    /// Fills the given layer of this texture with the given data.
+    ///
+    /// # Errors
+    /// Returns an error if the layer is not in the range `[0, depth - 1]` or if the length of the data does not correspond to the width, height and format specified at construction.
+    ///
+    pub fn fill<T: TextureDataType>(&mut self, layer: u32, data: &[T]) -> ThreeDResult<()> {
+        self.fill_partially(
+            layer,
+            ScissorBox::new_at_origo(self.width, self.height),
+            data,
+        )
+    }
+
+    ///
+    /// Fills the part of the given layer of this texture that is inside the given scissor box with the given data.
+    /// The scissor box is defined in pixels from the bottom left corner of the texture
+    /// while the data is given row by row starting from the top, in the same way as for [Texture2D::fill].
+    ///
+    /// # Errors
+    /// Returns an error if the layer is not in the range `[0, depth - 1]`, if the scissor box is not inside the texture
+    /// or if the length of the data does not correspond to the size of the scissor box and the format specified at construction.
+    ///
+    pub fn fill_partially<T: TextureDataType>(
+        &mut self,
+        layer: u32,
+        scissor_box: ScissorBox,
+        data: &[T],
+    ) -> ThreeDResult<()> {
+        if layer >= self.depth {
+            Err(CoreError::IndexOutOfRange(
+                layer as usize,
+                (self.depth as usize).saturating_sub(1),
+            ))?;
+        }
+        check_region(scissor_box, self.width, self.height)?;
+        check_data_length(
+            scissor_box.width,
+            scissor_box.height,
+            1,
+            self.data_byte_size,
+            data,
+        )?;
+        self.bind();
+        let mut data = data.to_owned();
+        flip_y(
+            &mut data,
+            scissor_box.width as usize,
+            scissor_box.height as usize,
+        );
+        unsafe {
+            self.context.tex_sub_image_3d(
+                crate::context::TEXTURE_2D_ARRAY,
+                0,
+                scissor_box.x,
+                scissor_box.y,
+                layer as i32,
+                scissor_box.width as i32,
+                scissor_box.height as i32,
+                1,
+                format_from_data_type::<T>(),
+                T::data_type(),
+                crate::context::PixelUnpackData::Slice(to_byte_slice(&data)),
+            );
+        }
+        self.generate_mip_maps();
+        self.context.error_check()
+    }
+
     ///
     /// Returns a [ColorTarget] which can be used to clear, write to and read from the given layers and mip level of this texture.
     /// Combine this together with a [DepthTarget] with [RenderTarget::new] to be able to write to both a depth and color target at the same time.
@@ -108,6 +380,61 @@ impl Texture2DArray {
         Ok(())
     }
 
+    ///
+    /// Reads the given layer and mip level of this texture back from the GPU and returns it as a [CpuTexture].
+    /// The data is returned in the same format as the texture, row by row starting from the top, in the same way as for [Texture2DArray::fill].
+    /// The filtering and wrapping parameters of the returned cpu texture are set to default values.
+    ///
+    pub fn to_cpu_texture(&self, layer: u32, mip_level: u32) -> ThreeDResult<CpuTexture> {
+        if layer >= self.depth {
+            Err(CoreError::IndexOutOfRange(
+                layer as usize,
+                (self.depth as usize).saturating_sub(1),
+            ))?;
+        }
+        let layers = [layer];
+        let color_target =
+            ColorTarget::new_texture_2d_array(&self.context, self, &layers, Some(mip_level));
+        Ok(CpuTexture {
+            data: texture_data_from_color_targets(
+                std::slice::from_ref(&color_target),
+                self.internal_format,
+                false,
+            )?,
+            width: color_target.width(),
+            height: color_target.height(),
+            ..Default::default()
+        })
+    }
+
+    ///
+    /// Returns the [Sampler] which describes how this texture is sampled in a shader.
+    ///
+    pub fn sampler(&self) -> Sampler {
+        self.sampler
+    }
+
+    ///
+    /// Changes how this texture is sampled in a shader, see [Sampler].
+    /// For example, set [Sampler::anisotropy] to improve the quality when the texture is viewed at grazing angles.
+    ///
+    /// # Errors
+    /// Returns an error if the minimum level of detail of the sampler is larger than the maximum level of detail, if the sampler uses a depth comparison function
+    /// or if this is an integer texture and the sampler uses linear interpolation or mip mapping.
+    ///
+    pub fn set_sampler(&mut self, sampler: Sampler) -> ThreeDResult<()> {
+        check_sampler(&sampler, is_integer_format(self.internal_format), false)?;
+        self.bind();
+        set_parameters(
+            &self.context,
+            crate::context::TEXTURE_2D_ARRAY,
+            &sampler,
+            self.number_of_mip_maps,
+        )?;
+        self.sampler = sampler;
+        Ok(())
+    }
+
     /// The width of this texture.
     pub fn width(&self) -> u32 {
         self.width
@@ -123,6 +450,27 @@ impl Texture2DArray {
         self.depth
     }
 
+    ///
+    /// Sets a debug label for this texture array which is used to identify it in [Context::resource_stats]
+    /// and in GPU debuggers if debug output is supported, see [Context::supports_debug_output].
+    ///
+    pub fn set_label(&self, label: &str) {
+        self.resource.set_label(label);
+        self.context.label_object(
+            label,
+            crate::context::TEXTURE,
+            crate::context::TEXTURE_BINDING_2D_ARRAY,
+            || self.bind(),
+        );
+    }
+
+    ///
+    /// Returns the debug label of this texture array, see [Texture2DArray::set_label].
+    ///
+    pub fn label(&self) -> Option<String> {
+        self.resource.label()
+    }
+
     pub(in crate::core) fn generate_mip_maps(&self) {
         if self.number_of_mip_maps > 1 {
             self.bind();
@@ -146,15 +494,14 @@ impl Texture2DArray {
     }
 
     pub(in crate::core) fn bind(&self) {
-        unsafe {
-            self.context
-                .bind_texture(crate::context::TEXTURE_2D_ARRAY, Some(self.id));
-        }
+        self.context
+            .bind_texture_cached(crate::context::TEXTURE_2D_ARRAY, self.id);
     }
 }
 
 impl Drop for Texture2DArray {
     fn drop(&mut self) {
+        self.context.forget_texture(self.id);
         unsafe {
             self.context.delete_texture(self.id);
         }
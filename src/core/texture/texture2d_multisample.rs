@@ -0,0 +1,132 @@
+use crate::core::texture::*;
+
+///
+/// A multisampled 2D color texture that can be rendered into.
+/// It cannot be sampled in a shader or read directly, instead it must be resolved into a non-multisampled target,
+/// for example a [Texture2D], using [ColorTarget::resolve_to].
+///
+pub struct Texture2DMultisample {
+    context: Context,
+    id: crate::context::Renderbuffer,
+    width: u32,
+    height: u32,
+    number_of_samples: u32,
+    resource: ResourceHandle,
+}
+
+impl Texture2DMultisample {
+    ///
+    /// Constructs a new multisampled 2D color texture with the given number of samples per pixel.
+    /// The format is determined by the generic [TextureDataType] parameter
+    /// (for example, if [u8; 4] is specified, the format is RGBA and the data type is byte).
+    ///
+    /// # Errors
+    /// Returns an error if the number of samples is zero or larger than the maximum number of samples supported by the graphics driver.
+    ///
+    pub fn new<T: TextureDataType>(
+        context: &Context,
+        width: u32,
+        height: u32,
+        number_of_samples: u32,
+    ) -> ThreeDResult<Self> {
+        check_number_of_samples(context, number_of_samples)?;
+        let id = generate_renderbuffer(context)?;
+        let texture = Self {
+            context: context.clone(),
+            id,
+            width,
+            height,
+            number_of_samples,
+            resource: context.track_resource(
+                ResourceKind::MultisampleTexture,
+                texture_byte_size(width, height, None, 1, 1, std::mem::size_of::<T>())
+                    * number_of_samples as usize,
+            ),
+        };
+        texture.bind();
+        unsafe {
+            context.renderbuffer_storage_multisample(
+                crate::context::RENDERBUFFER,
+                number_of_samples as i32,
+                T::internal_format(),
+                width as i32,
+                height as i32,
+            );
+            context.bind_renderbuffer(crate::context::RENDERBUFFER, None);
+        }
+        context.error_check()?;
+        Ok(texture)
+    }
+
+    ///
+    /// Returns a [ColorTarget] which can be used to clear and write to this texture and to resolve it into a non-multisampled target.
+    /// Combine this together with a [DepthTarget] with [RenderTarget::new] to be able to write to both a depth and color target at the same time.
+    ///
+    /// **Note:** [DepthTest] is disabled if not also writing to a depth texture.
+    ///
+    pub fn as_color_target(&mut self) -> ColorTarget<'_> {
+        ColorTarget::new_texture2d_multisample(&self.context, self)
+    }
+
+    /// The width of this texture.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The height of this texture.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The number of samples per pixel.
+    pub fn number_of_samples(&self) -> u32 {
+        self.number_of_samples
+    }
+
+    ///
+    /// Sets a debug label for this texture which is used to identify it in [Context::resource_stats]
+    /// and in GPU debuggers if debug output is supported, see [Context::supports_debug_output].
+    ///
+    pub fn set_label(&self, label: &str) {
+        self.resource.set_label(label);
+        self.context.label_object(
+            label,
+            crate::context::RENDERBUFFER,
+            crate::context::RENDERBUFFER_BINDING,
+            || self.bind(),
+        );
+    }
+
+    ///
+    /// Returns the debug label of this texture, see [Texture2DMultisample::set_label].
+    ///
+    pub fn label(&self) -> Option<String> {
+        self.resource.label()
+    }
+
+    pub(in crate::core) fn bind_as_color_target(&self, channel: u32) {
+        unsafe {
+            self.context.framebuffer_renderbuffer(
+                crate::context::FRAMEBUFFER,
+                crate::context::COLOR_ATTACHMENT0 + channel,
+                crate::context::RENDERBUFFER,
+                Some(self.id),
+            );
+        }
+    }
+
+    fn bind(&self) {
+        unsafe {
+            self.context
+                .bind_renderbuffer(crate::context::RENDERBUFFER, Some(self.id));
+        }
+    }
+}
+
+impl Drop for Texture2DMultisample {
+    fn drop(&mut self) {
+        unsafe {
+            self.context.delete_renderbuffer(self.id);
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use crate::core::texture::*;
 ///
-/// A 3D color texture.
+/// A 3D color texture that can be rendered into, one or more slices (along the z-axis) at a time, see [Texture3D::as_color_target], [Texture3D::write] and [RenderTarget3D].
+///
+/// **Note:** [DepthTest] is disabled if not also writing to a [DepthTarget].
+/// Use a [RenderTarget] or a [RenderTarget3D] to write to both color and depth.
 ///
 pub struct Texture3D {
     context: Context,
@@ -9,7 +12,10 @@ pub struct Texture3D {
     height: u32,
     depth: u32,
     number_of_mip_maps: u32,
+    sampler: Sampler,
     data_byte_size: usize,
+    internal_format: u32,
+    resource: ResourceHandle,
 }
 
 impl Texture3D {
@@ -69,6 +75,16 @@ impl Texture3D {
         wrap_t: Wrapping,
         wrap_r: Wrapping,
     ) -> ThreeDResult<Self> {
+        let sampler = Sampler {
+            min_filter,
+            mag_filter,
+            mip_map_filter,
+            wrap_s,
+            wrap_t,
+            wrap_r,
+            ..Default::default()
+        };
+        check_sampler(&sampler, is_integer_data_type::<T>(), false)?;
         let id = generate(context)?;
         let number_of_mip_maps =
             calculate_number_of_mip_maps(mip_map_filter, width, height, Some(depth));
@@ -79,22 +95,27 @@ impl Texture3D {
             height,
             depth,
             number_of_mip_maps,
+            sampler,
             data_byte_size: std::mem::size_of::<T>(),
+            internal_format: T::internal_format(),
+            resource: context.track_resource(
+                ResourceKind::Texture3D,
+                texture_byte_size(
+                    width,
+                    height,
+                    Some(depth),
+                    1,
+                    number_of_mip_maps,
+                    std::mem::size_of::<T>(),
+                ),
+            ),
         };
         texture.bind();
         set_parameters(
             context,
             crate::context::TEXTURE_3D,
-            min_filter,
-            mag_filter,
-            if number_of_mip_maps == 1 {
-                None
-            } else {
-                mip_map_filter
-            },
-            wrap_s,
-            wrap_t,
-            Some(wrap_r),
+            &texture.sampler,
+            number_of_mip_maps,
         )?;
         unsafe {
             context.tex_storage_3d(
@@ -146,6 +167,172 @@ impl Texture3D {
         self.context.error_check()
     }
 
+    ///
+    /// Fills the sub-box of this texture defined by the given scissor box in the xy-plane
+    /// and the `number_of_slices` slices along the z-axis starting at `first_slice` with the given data.
+    ///
+    /// # Errors
+    /// Returns an error if the sub-box is not inside the texture or if the length of the data does not correspond to the size of the sub-box and the format specified at construction.
+    ///
+    pub fn fill_partially<T: TextureDataType>(
+        &mut self,
+        scissor_box: ScissorBox,
+        first_slice: u32,
+        number_of_slices: u32,
+        data: &[T],
+    ) -> ThreeDResult<()> {
+        check_region(scissor_box, self.width, self.height)?;
+        let end_slice = first_slice as usize + number_of_slices as usize;
+        if end_slice > self.depth as usize {
+            Err(CoreError::IndexOutOfRange(
+                end_slice - 1,
+                (self.depth as usize).saturating_sub(1),
+            ))?;
+        }
+        check_data_length(
+            scissor_box.width,
+            scissor_box.height,
+            number_of_slices,
+            self.data_byte_size,
+            data,
+        )?;
+        self.bind();
+        unsafe {
+            self.context.tex_sub_image_3d(
+                crate::context::TEXTURE_3D,
+                0,
+                scissor_box.x,
+                scissor_box.y,
+                first_slice as i32,
+                scissor_box.width as i32,
+                scissor_box.height as i32,
+                number_of_slices as i32,
+                format_from_data_type::<T>(),
+                T::data_type(),
+                crate::context::PixelUnpackData::Slice(to_byte_slice(data)),
+            );
+        }
+        self.generate_mip_maps();
+        self.context.error_check()
+    }
+
+    ///
+    /// Returns a [ColorTarget] which can be used to clear, write to and read from the given slices (along the z-axis) and mip level of this texture.
+    /// Combine this together with a [DepthTarget] with [RenderTarget::new] to be able to write to both a depth and color target at the same time.
+    /// If `None` is specified as the mip level, the 0 level mip level is used and mip maps are generated after a write operation if a mip map filter is specified.
+    /// Otherwise, the given mip level is used and no mip maps are generated.
+    ///
+    /// Output at location *i* defined in the fragment shader is written to the slice at the *ith* index in `slices`,
+    /// so several slices can be computed on the GPU in one render call, for example when generating 3D noise or a signed distance field.
+    ///
+    /// **Note:** [DepthTest] is disabled if not also writing to a depth texture.
+    ///
+    pub fn as_color_target<'a>(
+        &'a mut self,
+        slices: &'a [u32],
+        mip_level: Option<u32>,
+    ) -> ColorTarget<'a> {
+        ColorTarget::new_texture3d(&self.context, self, slices, mip_level)
+    }
+
+    ///
+    /// Renders whatever rendered in the `render` closure into the slices (along the z-axis) of this texture defined by the input parameter `slices`.
+    /// Output at location *i* defined in the fragment shader is written to the slice at the *ith* index in `slices`.
+    /// Before writing, the slices are cleared based on the given clear state.
+    ///
+    /// **Note:** [DepthTest] is disabled if not also writing to a [DepthTarget].
+    /// Use a [RenderTarget] or a [RenderTarget3D] to write to both color and depth.
+    ///
+    pub fn write<'a, F: FnOnce() -> ThreeDResult<()>>(
+        &'a mut self,
+        slices: &'a [u32],
+        clear_state: ClearState,
+        render: F,
+    ) -> ThreeDResult<()> {
+        self.as_color_target(slices, None)
+            .as_render_target()?
+            .clear(clear_state)?
+            .write(render)?;
+        Ok(())
+    }
+
+    ///
+    /// Reads the given mip level of this texture back from the GPU and returns it as a [CpuTexture3D].
+    /// The data is returned in the same format and order as given to [Texture3D::fill].
+    /// The filtering and wrapping parameters of the returned cpu texture are set to default values.
+    ///
+    pub fn to_cpu_texture(&self, mip_level: u32) -> ThreeDResult<CpuTexture3D> {
+        let depth = (self.depth / 2u32.pow(mip_level)).max(1);
+        let slices = (0..depth).map(|slice| [slice]).collect::<Vec<_>>();
+        let color_targets = slices
+            .iter()
+            .map(|slice| ColorTarget::new_texture3d(&self.context, self, slice, Some(mip_level)))
+            .collect::<Vec<_>>();
+        let data = texture_data_from_color_targets(&color_targets, self.internal_format, true)?;
+        Ok(CpuTexture3D {
+            data,
+            width: (self.width / 2u32.pow(mip_level)).max(1),
+            height: (self.height / 2u32.pow(mip_level)).max(1),
+            depth,
+            ..Default::default()
+        })
+    }
+
+    ///
+    /// Reads the given slice (along the z-axis) and mip level of this texture back from the GPU and returns it as a [CpuTexture].
+    /// The data is returned in the same format and order as given to [Texture3D::fill].
+    /// The filtering and wrapping parameters of the returned cpu texture are set to default values.
+    ///
+    pub fn slice_to_cpu_texture(&self, slice: u32, mip_level: u32) -> ThreeDResult<CpuTexture> {
+        if slice >= self.depth {
+            Err(CoreError::IndexOutOfRange(
+                slice as usize,
+                (self.depth as usize).saturating_sub(1),
+            ))?;
+        }
+        let slices = [slice];
+        let color_target =
+            ColorTarget::new_texture3d(&self.context, self, &slices, Some(mip_level));
+        Ok(CpuTexture {
+            data: texture_data_from_color_targets(
+                std::slice::from_ref(&color_target),
+                self.internal_format,
+                true,
+            )?,
+            width: color_target.width(),
+            height: color_target.height(),
+            ..Default::default()
+        })
+    }
+
+    ///
+    /// Returns the [Sampler] which describes how this texture is sampled in a shader.
+    ///
+    pub fn sampler(&self) -> Sampler {
+        self.sampler
+    }
+
+    ///
+    /// Changes how this texture is sampled in a shader, see [Sampler].
+    /// For example, set [Sampler::anisotropy] to improve the quality when the texture is viewed at grazing angles.
+    ///
+    /// # Errors
+    /// Returns an error if the minimum level of detail of the sampler is larger than the maximum level of detail, if the sampler uses a depth comparison function
+    /// or if this is an integer texture and the sampler uses linear interpolation or mip mapping.
+    ///
+    pub fn set_sampler(&mut self, sampler: Sampler) -> ThreeDResult<()> {
+        check_sampler(&sampler, is_integer_format(self.internal_format), false)?;
+        self.bind();
+        set_parameters(
+            &self.context,
+            crate::context::TEXTURE_3D,
+            &sampler,
+            self.number_of_mip_maps,
+        )?;
+        self.sampler = sampler;
+        Ok(())
+    }
+
     /// The width of this texture.
     pub fn width(&self) -> u32 {
         self.width
@@ -161,7 +348,28 @@ impl Texture3D {
         self.depth
     }
 
-    fn generate_mip_maps(&self) {
+    ///
+    /// Sets a debug label for this texture which is used to identify it in [Context::resource_stats]
+    /// and in GPU debuggers if debug output is supported, see [Context::supports_debug_output].
+    ///
+    pub fn set_label(&self, label: &str) {
+        self.resource.set_label(label);
+        self.context.label_object(
+            label,
+            crate::context::TEXTURE,
+            crate::context::TEXTURE_BINDING_3D,
+            || self.bind(),
+        );
+    }
+
+    ///
+    /// Returns the debug label of this texture, see [Texture3D::set_label].
+    ///
+    pub fn label(&self) -> Option<String> {
+        self.resource.label()
+    }
+
+    pub(in crate::core) fn generate_mip_maps(&self) {
         if self.number_of_mip_maps > 1 {
             self.bind();
             unsafe {
@@ -169,16 +377,28 @@ impl Texture3D {
             }
         }
     }
-    pub(in crate::core) fn bind(&self) {
+
+    pub(in crate::core) fn bind_as_color_target(&self, slice: u32, channel: u32, mip_level: u32) {
         unsafe {
-            self.context
-                .bind_texture(crate::context::TEXTURE_3D, Some(self.id));
+            self.context.framebuffer_texture_layer(
+                crate::context::DRAW_FRAMEBUFFER,
+                crate::context::COLOR_ATTACHMENT0 + channel,
+                Some(self.id),
+                mip_level as i32,
+                slice as i32,
+            );
         }
     }
+
+    pub(in crate::core) fn bind(&self) {
+        self.context
+            .bind_texture_cached(crate::context::TEXTURE_3D, self.id);
+    }
 }
 
 impl Drop for Texture3D {
     fn drop(&mut self) {
+        self.context.forget_texture(self.id);
         unsafe {
             self.context.delete_texture(self.id);
         }
@@ -115,8 +115,11 @@ pub struct TextureCubeMap {
     width: u32,
     height: u32,
     number_of_mip_maps: u32,
+    sampler: Sampler,
     is_hdr: bool,
     data_byte_size: usize,
+    internal_format: u32,
+    resource: ResourceHandle,
 }
 
 impl TextureCubeMap {
@@ -269,6 +272,46 @@ impl TextureCubeMap {
         }
     }
 
+    ///
+    /// Creates a new cube map texture from the given [CpuTexture]s which are assumed to be in sRGB color space.
+    /// The data is stored in sRGB color space on the GPU and is automatically converted to linear color space when sampled in a shader,
+    /// which means that filtering and mip mapping is done correctly in linear color space.
+    ///
+    /// # Errors
+    /// Returns an error if the data of any of the cpu textures is not RGB or RGBA bytes.
+    ///
+    pub fn new_srgb(
+        context: &Context,
+        right: &CpuTexture,
+        left: &CpuTexture,
+        top: &CpuTexture,
+        bottom: &CpuTexture,
+        front: &CpuTexture,
+        back: &CpuTexture,
+    ) -> ThreeDResult<Self> {
+        let mut texture = Self::new_empty_with_internal_format::<[u8; 4]>(
+            context,
+            front.width,
+            front.height,
+            front.min_filter,
+            front.mag_filter,
+            front.mip_map_filter,
+            front.wrap_s,
+            front.wrap_t,
+            right.wrap_s,
+            crate::context::SRGB8_ALPHA8,
+        )?;
+        texture.fill(
+            &srgba_data(right)?,
+            &srgba_data(left)?,
+            &srgba_data(top)?,
+            &srgba_data(bottom)?,
+            &srgba_data(front)?,
+            &srgba_data(back)?,
+        )?;
+        Ok(texture)
+    }
+
     fn new_with_data<T: TextureDataType>(
         context: &Context,
         cpu_texture: &CpuTexture,
@@ -316,6 +359,43 @@ impl TextureCubeMap {
         wrap_t: Wrapping,
         wrap_r: Wrapping,
     ) -> ThreeDResult<Self> {
+        Self::new_empty_with_internal_format::<T>(
+            context,
+            width,
+            height,
+            min_filter,
+            mag_filter,
+            mip_map_filter,
+            wrap_s,
+            wrap_t,
+            wrap_r,
+            T::internal_format(),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn new_empty_with_internal_format<T: TextureDataType>(
+        context: &Context,
+        width: u32,
+        height: u32,
+        min_filter: Interpolation,
+        mag_filter: Interpolation,
+        mip_map_filter: Option<Interpolation>,
+        wrap_s: Wrapping,
+        wrap_t: Wrapping,
+        wrap_r: Wrapping,
+        internal_format: u32,
+    ) -> ThreeDResult<Self> {
+        let sampler = Sampler {
+            min_filter,
+            mag_filter,
+            mip_map_filter,
+            wrap_s,
+            wrap_t,
+            wrap_r,
+            ..Default::default()
+        };
+        check_sampler(&sampler, is_integer_data_type::<T>(), false)?;
         let id = generate(context)?;
         let number_of_mip_maps = calculate_number_of_mip_maps(mip_map_filter, width, height, None);
         let texture = Self {
@@ -324,29 +404,34 @@ impl TextureCubeMap {
             width,
             height,
             number_of_mip_maps,
+            sampler,
             is_hdr: std::mem::size_of::<T>() as u32 / T::size() > 1,
             data_byte_size: std::mem::size_of::<T>(),
+            internal_format,
+            resource: context.track_resource(
+                ResourceKind::TextureCubeMap,
+                texture_byte_size(
+                    width,
+                    height,
+                    None,
+                    6,
+                    number_of_mip_maps,
+                    std::mem::size_of::<T>(),
+                ),
+            ),
         };
         texture.bind();
         set_parameters(
             context,
             crate::context::TEXTURE_CUBE_MAP,
-            min_filter,
-            mag_filter,
-            if number_of_mip_maps == 1 {
-                None
-            } else {
-                mip_map_filter
-            },
-            wrap_s,
-            wrap_t,
-            Some(wrap_r),
+            &texture.sampler,
+            number_of_mip_maps,
         )?;
         unsafe {
             context.tex_storage_2d(
                 crate::context::TEXTURE_CUBE_MAP,
                 number_of_mip_maps as i32,
-                T::internal_format(),
+                internal_format,
                 width as i32,
                 height as i32,
             );
@@ -407,6 +492,44 @@ impl TextureCubeMap {
         self.context.error_check()
     }
 
+    ///
+    /// Fills the part of the given side of the cube map texture that is inside the given scissor box with the given data.
+    ///
+    /// # Errors
+    /// Returns an error if the scissor box is not inside the texture or if the length of the data does not correspond to the size of the scissor box and the format specified at construction.
+    ///
+    pub fn fill_partially<T: TextureDataType>(
+        &mut self,
+        side: CubeMapSide,
+        scissor_box: ScissorBox,
+        data: &[T],
+    ) -> ThreeDResult<()> {
+        check_region(scissor_box, self.width, self.height)?;
+        check_data_length(
+            scissor_box.width,
+            scissor_box.height,
+            1,
+            self.data_byte_size,
+            data,
+        )?;
+        self.bind();
+        unsafe {
+            self.context.tex_sub_image_2d(
+                side.to_const(),
+                0,
+                scissor_box.x,
+                scissor_box.y,
+                scissor_box.width as i32,
+                scissor_box.height as i32,
+                format_from_data_type::<T>(),
+                T::data_type(),
+                crate::context::PixelUnpackData::Slice(to_byte_slice(data)),
+            );
+        }
+        self.generate_mip_maps();
+        self.context.error_check()
+    }
+
     ///
     /// Creates a new cube texture generated from the equirectangular texture given as input.
     ///
@@ -515,6 +638,130 @@ impl TextureCubeMap {
         Ok(())
     }
 
+    ///
+    /// Reads the given side and mip level of this texture back from the GPU and returns it as a [CpuTexture].
+    /// The data is returned in the same format and order as given to [TextureCubeMap::fill].
+    /// The filtering and wrapping parameters of the returned cpu texture are set to default values.
+    ///
+    pub fn to_cpu_texture(&self, side: CubeMapSide, mip_level: u32) -> ThreeDResult<CpuTexture> {
+        let color_target =
+            ColorTarget::new_texture_cube_map(&self.context, self, side, Some(mip_level));
+        Ok(CpuTexture {
+            data: texture_data_from_color_targets(
+                std::slice::from_ref(&color_target),
+                self.internal_format,
+                true,
+            )?,
+            width: color_target.width(),
+            height: color_target.height(),
+            ..Default::default()
+        })
+    }
+
+    ///
+    /// Reads all six sides of the given mip level of this texture back from the GPU and returns them as [CpuTexture]s
+    /// in the same order as given to [TextureCubeMap::new] (right, left, top, bottom, front and back).
+    ///
+    pub fn to_cpu_textures(&self, mip_level: u32) -> ThreeDResult<Vec<CpuTexture>> {
+        CubeMapSide::iter()
+            .map(|side| self.to_cpu_texture(side, mip_level))
+            .collect()
+    }
+
+    ///
+    /// Converts this cube map texture into an equirectangular [CpuTexture] with the given size, ie. the inverse of [TextureCubeMap::new_from_equirectangular].
+    /// The returned texture contains RGBA 16 bit float data if this cube map contains HDR data (see [TextureCubeMap::is_hdr]) and RGBA bytes otherwise,
+    /// which are in sRGB color space if this is an sRGB cube map (see [TextureCubeMap::is_srgb]).
+    /// The width is usually twice the height.
+    ///
+    pub fn to_equirectangular(&self, width: u32, height: u32) -> ThreeDResult<CpuTexture> {
+        let mut map = if self.is_hdr {
+            Texture2D::new_empty::<[f16; 4]>(
+                &self.context,
+                width,
+                height,
+                Interpolation::Nearest,
+                Interpolation::Nearest,
+                None,
+                Wrapping::ClampToEdge,
+                Wrapping::ClampToEdge,
+            )?
+        } else if self.is_srgb() {
+            Texture2D::new_empty_srgb(
+                &self.context,
+                width,
+                height,
+                Interpolation::Nearest,
+                Interpolation::Nearest,
+                None,
+                Wrapping::ClampToEdge,
+                Wrapping::ClampToEdge,
+            )?
+        } else {
+            Texture2D::new_empty::<[u8; 4]>(
+                &self.context,
+                width,
+                height,
+                Interpolation::Nearest,
+                Interpolation::Nearest,
+                None,
+                Wrapping::ClampToEdge,
+                Wrapping::ClampToEdge,
+            )?
+        };
+        let fragment_shader_source = "uniform samplerCube cubeMap;
+
+            in vec2 uv;
+            layout (location = 0) out vec4 outColor;
+
+            void main()
+            {
+                float phi = (uv.x - 0.5) * 6.2831853;
+                float theta = (uv.y - 0.5) * 3.1415927;
+                vec3 direction = vec3(cos(theta) * cos(phi), sin(theta), cos(theta) * sin(phi));
+                outColor = vec4(texture(cubeMap, direction).rgb, 1.0);
+            }";
+        let effect = ImageEffect::new(&self.context, fragment_shader_source)?;
+        effect.use_texture_cube("cubeMap", self)?;
+        map.as_color_target(None)
+            .clear(ClearState::default())?
+            .write(|| {
+                effect.render(
+                    RenderStates::default(),
+                    Viewport::new_at_origo(width, height),
+                )
+            })?;
+        map.to_cpu_texture(0)
+    }
+
+    ///
+    /// Returns the [Sampler] which describes how this texture is sampled in a shader.
+    ///
+    pub fn sampler(&self) -> Sampler {
+        self.sampler
+    }
+
+    ///
+    /// Changes how this texture is sampled in a shader, see [Sampler].
+    /// For example, set [Sampler::anisotropy] to improve the quality when the texture is viewed at grazing angles.
+    ///
+    /// # Errors
+    /// Returns an error if the minimum level of detail of the sampler is larger than the maximum level of detail, if the sampler uses a depth comparison function
+    /// or if this is an integer texture and the sampler uses linear interpolation or mip mapping.
+    ///
+    pub fn set_sampler(&mut self, sampler: Sampler) -> ThreeDResult<()> {
+        check_sampler(&sampler, is_integer_format(self.internal_format), false)?;
+        self.bind();
+        set_parameters(
+            &self.context,
+            crate::context::TEXTURE_CUBE_MAP,
+            &sampler,
+            self.number_of_mip_maps,
+        )?;
+        self.sampler = sampler;
+        Ok(())
+    }
+
     /// The width of this texture.
     pub fn width(&self) -> u32 {
         self.width
@@ -530,6 +777,32 @@ impl TextureCubeMap {
         self.is_hdr
     }
 
+    /// Whether this cube map stores the data in sRGB color space, see [TextureCubeMap::new_srgb].
+    pub fn is_srgb(&self) -> bool {
+        self.internal_format == crate::context::SRGB8_ALPHA8
+    }
+
+    ///
+    /// Sets a debug label for this texture which is used to identify it in [Context::resource_stats]
+    /// and in GPU debuggers if debug output is supported, see [Context::supports_debug_output].
+    ///
+    pub fn set_label(&self, label: &str) {
+        self.resource.set_label(label);
+        self.context.label_object(
+            label,
+            crate::context::TEXTURE,
+            crate::context::TEXTURE_BINDING_CUBE_MAP,
+            || self.bind(),
+        );
+    }
+
+    ///
+    /// Returns the debug label of this texture, see [TextureCubeMap::set_label].
+    ///
+    pub fn label(&self) -> Option<String> {
+        self.resource.label()
+    }
+
     pub(in crate::core) fn generate_mip_maps(&self) {
         if self.number_of_mip_maps > 1 {
             self.bind();
@@ -558,15 +831,14 @@ impl TextureCubeMap {
     }
 
     pub(in crate::core) fn bind(&self) {
-        unsafe {
-            self.context
-                .bind_texture(crate::context::TEXTURE_CUBE_MAP, Some(self.id));
-        }
+        self.context
+            .bind_texture_cached(crate::context::TEXTURE_CUBE_MAP, self.id);
     }
 }
 
 impl Drop for TextureCubeMap {
     fn drop(&mut self) {
+        self.context.forget_texture(self.id);
         unsafe {
             self.context.delete_texture(self.id);
         }
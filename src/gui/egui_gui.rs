@@ -32,13 +32,11 @@ impl GUI {
             program: Program::from_source(
                 context,
                 &format!(
-                    "{}{}",
-                    include_str!("../core/shared.frag"),
+                    "#include \"shared.frag\"\n{}",
                     include_str!("shaders/egui.vert")
                 ),
                 &format!(
-                    "{}{}",
-                    include_str!("../core/shared.frag"),
+                    "#include \"shared.frag\"\n{}",
                     include_str!("shaders/egui.frag")
                 ),
             )?,
@@ -12,6 +12,10 @@ mod forward_pipeline;
 #[doc(inline)]
 pub use forward_pipeline::*;
 
+mod occlusion_culling;
+#[doc(inline)]
+pub use occlusion_culling::*;
+
 mod deferred_pipeline;
 #[doc(inline)]
 pub use deferred_pipeline::*;
@@ -123,27 +123,34 @@ impl DeferredPipeline {
             Wrapping::ClampToEdge,
             DepthFormat::Depth32F,
         )?);
-        RenderTarget::new(
-            self.geometry_pass_texture
-                .as_mut()
-                .unwrap()
-                .as_color_target(&[0, 1, 2], None),
-            self.geometry_pass_depth_texture
-                .as_mut()
-                .unwrap()
-                .as_depth_target(),
-        )?
-        .clear(ClearState::default())?
-        .write(|| {
-            for (geometry, material) in objects
-                .iter()
-                .filter(|(g, _)| self.camera.in_frustum(&g.aabb()))
-            {
-                geometry.render_with_material(material, &self.camera, &[])?;
-            }
+        self.geometry_pass_texture()
+            .set_label("DeferredPipeline gbuffer");
+        self.geometry_pass_depth_texture()
+            .set_label("DeferredPipeline depth");
+        let context = self.context.clone();
+        context.debug_group("DeferredPipeline render pass", || {
+            RenderTarget::new(
+                self.geometry_pass_texture
+                    .as_mut()
+                    .unwrap()
+                    .as_color_target(&[0, 1, 2], None),
+                self.geometry_pass_depth_texture
+                    .as_mut()
+                    .unwrap()
+                    .as_depth_target(),
+            )?
+            .clear(ClearState::default())?
+            .write(|| {
+                for (geometry, material) in objects
+                    .iter()
+                    .filter(|(g, _)| self.camera.in_frustum(&g.aabb()))
+                {
+                    geometry.render_with_material(material, &self.camera, &[])?;
+                }
+                Ok(())
+            })?;
             Ok(())
-        })?;
-        Ok(())
+        })
     }
 
     ///
@@ -166,25 +173,28 @@ impl DeferredPipeline {
         );
         fragment_shader.push_str(include_str!("material/shaders/deferred_lighting.frag"));
 
-        self.context.effect(&fragment_shader, |effect| {
-            effect.use_uniform_if_required("cameraPosition", camera.position())?;
-            for (i, light) in lights.iter().enumerate() {
-                light.use_uniforms(effect, i as u32)?;
-            }
-            effect.use_texture_array("gbuffer", self.geometry_pass_texture())?;
-            effect.use_depth_texture("depthMap", self.geometry_pass_depth_texture())?;
-            effect.use_uniform_if_required(
-                "viewProjectionInverse",
-                (camera.projection() * camera.view()).invert().unwrap(),
-            )?;
-            effect.use_uniform("debug_type", self.debug_type as i32)?;
-            if self.debug_type == DebugType::DEPTH {
-                effect.use_uniform("zNear", camera.z_near())?;
-                effect.use_uniform("zFar", camera.z_far())?;
-            }
-            effect.apply(render_states, camera.viewport())?;
-            Ok(())
-        })
+        self.context
+            .debug_group("DeferredPipeline lighting pass", || {
+                self.context.effect(&fragment_shader, |effect| {
+                    effect.use_uniform_if_required("cameraPosition", camera.position())?;
+                    for (i, light) in lights.iter().enumerate() {
+                        light.use_uniforms(effect, i as u32)?;
+                    }
+                    effect.use_texture_array("gbuffer", self.geometry_pass_texture())?;
+                    effect.use_depth_texture("depthMap", self.geometry_pass_depth_texture())?;
+                    effect.use_uniform_if_required(
+                        "viewProjectionInverse",
+                        (camera.projection() * camera.view()).invert().unwrap(),
+                    )?;
+                    effect.use_uniform("debug_type", self.debug_type as i32)?;
+                    if self.debug_type == DebugType::DEPTH {
+                        effect.use_uniform("zNear", camera.z_near())?;
+                        effect.use_uniform("zFar", camera.z_far())?;
+                    }
+                    effect.apply(render_states, camera.viewport())?;
+                    Ok(())
+                })
+            })
     }
 
     /// Returns the geometry pass texture
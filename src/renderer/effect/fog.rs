@@ -30,8 +30,7 @@ impl FogEffect {
             image_effect: ImageEffect::new(
                 context,
                 &format!(
-                    "{}{}",
-                    include_str!("../../core/shared.frag"),
+                    "#include \"shared.frag\"\n{}",
                     include_str!("shaders/fog.frag")
                 ),
             )?,
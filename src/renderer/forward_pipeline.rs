@@ -29,7 +29,9 @@ impl ForwardPipeline {
         objects: &[&dyn Object],
         lights: &[&dyn Light],
     ) -> ThreeDResult<()> {
-        render_pass(camera, objects, lights)
+        self.context.debug_group("ForwardPipeline render pass", || {
+            render_pass(camera, objects, lights)
+        })
     }
 
     ///
@@ -37,6 +39,12 @@ impl ForwardPipeline {
     /// Must be called in the callback given as input to a [RenderTarget] or [DepthTarget] write method.
     ///
     pub fn depth_pass(&self, camera: &Camera, objects: &[&dyn Object]) -> ThreeDResult<()> {
+        self.context.debug_group("ForwardPipeline depth pass", || {
+            self.render_depth(camera, objects)
+        })
+    }
+
+    fn render_depth(&self, camera: &Camera, objects: &[&dyn Object]) -> ThreeDResult<()> {
         let depth_material = DepthMaterial {
             render_states: RenderStates {
                 write_mask: WriteMask::DEPTH,
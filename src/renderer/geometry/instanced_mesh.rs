@@ -73,6 +73,16 @@ impl InstancedMesh {
         self.texture_transform = texture_transform;
     }
 
+    ///
+    /// Returns the vertex buffer with the given name, for example `position` or `normal`, if this instanced mesh has data for that attribute.
+    /// This can be used to update the vertex data directly on the GPU, for example by writing the output of a transform feedback program into it,
+    /// see [Program::draw_arrays_with_transform_feedback].
+    /// **Note:** The bounding box is not updated when the vertex data is changed this way.
+    ///
+    pub fn vertex_buffer_mut(&mut self, name: &str) -> Option<&mut VertexBuffer> {
+        self.vertex_buffers.get_mut(name)
+    }
+
     /// Returns the number of instances that is rendered.
     pub fn instance_count(&self) -> u32 {
         self.instance_count
@@ -93,7 +103,7 @@ impl InstancedMesh {
         #[cfg(debug_assertions)]
         instances.validate()?;
         self.instance_count = instances.count();
-        self.instance_buffers.clear();
+        let mut old_buffers = std::mem::take(&mut self.instance_buffers);
         self.instance_transforms = (0..self.instance_count as usize)
             .map(|i| {
                 Mat4::from_translation(instances.translations[i])
@@ -111,10 +121,11 @@ impl InstancedMesh {
             .collect::<Vec<_>>();
 
         if instances.rotations.is_none() && instances.scales.is_none() {
-            self.instance_buffers.insert(
-                "instance_translation".to_string(),
-                InstanceBuffer::new_with_data(&self.context, &instances.translations)?,
-            );
+            self.set_instance_buffer(
+                &mut old_buffers,
+                "instance_translation",
+                &instances.translations,
+            )?;
         } else {
             let mut row1 = Vec::new();
             let mut row2 = Vec::new();
@@ -142,18 +153,9 @@ impl InstancedMesh {
                 ));
             }
 
-            self.instance_buffers.insert(
-                "row1".to_string(),
-                InstanceBuffer::new_with_data(&self.context, &row1)?,
-            );
-            self.instance_buffers.insert(
-                "row2".to_string(),
-                InstanceBuffer::new_with_data(&self.context, &row2)?,
-            );
-            self.instance_buffers.insert(
-                "row3".to_string(),
-                InstanceBuffer::new_with_data(&self.context, &row3)?,
-            );
+            self.set_instance_buffer(&mut old_buffers, "row1", &row1)?;
+            self.set_instance_buffer(&mut old_buffers, "row2", &row2)?;
+            self.set_instance_buffer(&mut old_buffers, "row3", &row3)?;
         }
 
         if let Some(texture_transforms) = &instances.texture_transforms {
@@ -171,25 +173,43 @@ impl InstancedMesh {
                     texture_transform.z.y,
                 ));
             }
-            self.instance_buffers.insert(
-                "tex_transform_row1".to_string(),
-                InstanceBuffer::new_with_data(&self.context, &instance_tex_transform1)?,
-            );
-            self.instance_buffers.insert(
-                "tex_transform_row2".to_string(),
-                InstanceBuffer::new_with_data(&self.context, &instance_tex_transform2)?,
-            );
+            self.set_instance_buffer(
+                &mut old_buffers,
+                "tex_transform_row1",
+                &instance_tex_transform1,
+            )?;
+            self.set_instance_buffer(
+                &mut old_buffers,
+                "tex_transform_row2",
+                &instance_tex_transform2,
+            )?;
         }
         if let Some(instance_colors) = &instances.colors {
-            self.instance_buffers.insert(
-                "instance_color".to_string(),
-                InstanceBuffer::new_with_data(&self.context, &instance_colors)?,
-            );
+            self.set_instance_buffer(&mut old_buffers, "instance_color", instance_colors)?;
         }
         self.update_aabb();
         Ok(())
     }
 
+    ///
+    /// Fills the instance buffer with the given name, reusing the buffer from the previous instances if it exists to avoid allocating a new buffer.
+    ///
+    fn set_instance_buffer<T: BufferDataType>(
+        &mut self,
+        old_buffers: &mut HashMap<String, InstanceBuffer>,
+        name: &str,
+        data: &[T],
+    ) -> ThreeDResult<()> {
+        let buffer = if let Some(mut buffer) = old_buffers.remove(name) {
+            buffer.fill(data)?;
+            buffer
+        } else {
+            InstanceBuffer::new_with_data(&self.context, data)?
+        };
+        self.instance_buffers.insert(name.to_string(), buffer);
+        Ok(())
+    }
+
     fn update_aabb(&mut self) {
         let mut aabb = AxisAlignedBoundingBox::EMPTY;
         for i in 0..self.instance_count as usize {
@@ -207,7 +227,7 @@ impl InstancedMesh {
         let use_uvs = fragment_shader_source.find("in vec2 uvs;").is_some();
         let use_colors = fragment_shader_source.find("in vec4 col;").is_some();
         Ok(format!(
-            "{}{}{}{}{}{}{}{}{}",
+            "{}{}{}{}{}{}{}#include \"shared.frag\"\n{}",
             if self.instance_buffers.contains_key("instance_translation") {
                 "#define USE_INSTANCE_TRANSLATIONS\n"
             } else {
@@ -250,7 +270,6 @@ impl InstancedMesh {
             } else {
                 ""
             },
-            include_str!("../../core/shared.frag"),
             include_str!("shaders/mesh.vert"),
         ))
     }
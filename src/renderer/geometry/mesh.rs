@@ -86,6 +86,16 @@ impl Mesh {
         self.texture_transform = texture_transform;
     }
 
+    ///
+    /// Returns the vertex buffer with the given name, for example `position` or `normal`, if this mesh has data for that attribute.
+    /// This can be used to update the vertex data directly on the GPU, for example by writing the output of a transform feedback program into it,
+    /// see [Program::draw_arrays_with_transform_feedback].
+    /// **Note:** The bounding box is not updated when the vertex data is changed this way.
+    ///
+    pub fn vertex_buffer_mut(&mut self, name: &str) -> Option<&mut VertexBuffer> {
+        self.vertex_buffers.get_mut(name)
+    }
+
     fn vertex_shader_source(fragment_shader_source: &str) -> ThreeDResult<String> {
         let use_positions = fragment_shader_source.find("in vec3 pos;").is_some();
         let use_normals = fragment_shader_source.find("in vec3 nor;").is_some();
@@ -93,7 +103,7 @@ impl Mesh {
         let use_uvs = fragment_shader_source.find("in vec2 uvs;").is_some();
         let use_colors = fragment_shader_source.find("in vec4 col;").is_some();
         Ok(format!(
-            "{}{}{}{}{}{}{}",
+            "{}{}{}{}{}#include \"shared.frag\"\n{}",
             if use_positions {
                 "#define USE_POSITIONS\n"
             } else {
@@ -118,7 +128,6 @@ impl Mesh {
             } else {
                 ""
             },
-            include_str!("../../core/shared.frag"),
             include_str!("shaders/mesh.vert"),
         ))
     }
@@ -48,6 +48,18 @@ impl Default for Attenuation {
     }
 }
 
+///
+/// Writes the attenuation as a `vec3` containing the constant, linear and quadratic factors in a uniform block declared with `layout (std140)`.
+///
+impl Std140 for Attenuation {
+    fn alignment() -> usize {
+        16
+    }
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        vec3(self.constant, self.linear, self.quadratic).write_std140(writer);
+    }
+}
+
 /// Represents a light source.
 pub trait Light {
     /// The fragment shader source for calculating this lights contribution to the color in a fragment.
@@ -116,8 +128,7 @@ impl<T: Light> Light for std::rc::Rc<std::cell::RefCell<T>> {
 ///
 pub fn lights_shader_source(lights: &[&dyn Light], lighting_model: LightingModel) -> String {
     let mut shader_source = lighting_model_shader(lighting_model).to_string();
-    shader_source.push_str(include_str!("../core/shared.frag"));
-    shader_source.push_str(include_str!("light/shaders/light_shared.frag"));
+    shader_source.push_str("#include \"shared.frag\"\n#include \"light_shared.frag\"\n");
     let mut dir_fun = String::new();
     for (i, light) in lights.iter().enumerate() {
         shader_source.push_str(&light.shader_source(i as u32));
@@ -138,6 +149,11 @@ pub fn lights_shader_source(lights: &[&dyn Light], lighting_model: LightingModel
     shader_source
 }
 
+///
+/// The depth bias used when generating shadow maps to avoid shadow acne.
+///
+const SHADOW_MAP_DEPTH_BIAS: DepthBias = DepthBias::new(1.1, 4.0);
+
 fn shadow_matrix(camera: &Camera) -> Mat4 {
     let bias_matrix = crate::Mat4::new(
         0.5, 0.0, 0.0, 0.0, 0.0, 0.5, 0.0, 0.0, 0.0, 0.0, 0.5, 0.0, 0.5, 0.5, 0.5, 1.0,
@@ -156,8 +172,8 @@ fn compute_up_direction(direction: Vec3) -> Vec3 {
 use crate::renderer::{LightingModel, NormalDistributionFunction};
 pub(crate) fn lighting_model_shader(lighting_model: LightingModel) -> &'static str {
     match lighting_model {
-        LightingModel::Phong => "#define PHONG",
-        LightingModel::Blinn => "#define BLINN",
+        LightingModel::Phong => "#define PHONG\n",
+        LightingModel::Blinn => "#define BLINN\n",
         LightingModel::Cook(normal, _) => match normal {
             NormalDistributionFunction::Blinn => "#define COOK\n#define COOK_BLINN\n",
             NormalDistributionFunction::Beckmann => "#define COOK\n#define COOK_BECKMANN\n",
@@ -98,6 +98,19 @@ impl Light for AmbientLight {
     }
 }
 
+///
+/// Writes the color of the ambient light multiplied by the intensity as a `vec3` in a uniform block declared with `layout (std140)`.
+/// The environment is not written since textures cannot be part of a uniform block.
+///
+impl Std140 for AmbientLight {
+    fn alignment() -> usize {
+        16
+    }
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        (self.color.to_vec3() * self.intensity).write_std140(writer);
+    }
+}
+
 impl Default for AmbientLight {
     fn default() -> Self {
         Self {
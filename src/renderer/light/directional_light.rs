@@ -89,23 +89,29 @@ impl DirectionalLight {
             Wrapping::ClampToEdge,
             DepthFormat::Depth32F,
         )?;
+        shadow_texture.set_label("DirectionalLight shadow map");
         let depth_material = DepthMaterial {
             render_states: RenderStates {
                 write_mask: WriteMask::DEPTH,
+                depth_bias: SHADOW_MAP_DEPTH_BIAS,
                 ..Default::default()
             },
             ..Default::default()
         };
-        shadow_texture
-            .as_depth_target()
-            .clear(ClearState::default())?
-            .write(|| {
-                for geometry in geometries
-                    .iter()
-                    .filter(|g| shadow_camera.in_frustum(&g.aabb()))
-                {
-                    geometry.render_with_material(&depth_material, &shadow_camera, &[])?;
-                }
+        self.context
+            .debug_group("DirectionalLight shadow map", || {
+                shadow_texture
+                    .as_depth_target()
+                    .clear(ClearState::default())?
+                    .write(|| {
+                        for geometry in geometries
+                            .iter()
+                            .filter(|g| shadow_camera.in_frustum(&g.aabb()))
+                        {
+                            geometry.render_with_material(&depth_material, &shadow_camera, &[])?;
+                        }
+                        Ok(())
+                    })?;
                 Ok(())
             })?;
         self.shadow_texture = Some(shadow_texture);
@@ -166,3 +172,25 @@ impl Light for DirectionalLight {
         Ok(())
     }
 }
+
+///
+/// Writes the directional light as the following struct in a uniform block declared with `layout (std140)`, where the color is multiplied by the intensity:
+///
+/// ```notrust
+/// struct DirectionalLight
+/// {
+///     vec3 color;
+///     vec3 direction;
+/// };
+/// ```
+///
+impl Std140 for DirectionalLight {
+    fn alignment() -> usize {
+        16
+    }
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        writer.write(&(self.color.to_vec3() * self.intensity));
+        writer.write(&self.direction.normalize());
+        writer.align(16);
+    }
+}
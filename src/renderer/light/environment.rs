@@ -54,8 +54,7 @@ impl Environment {
         )?;
         {
             let fragment_shader_source = format!(
-                "{}{}",
-                include_str!("../../core/shared.frag"),
+                "#include \"shared.frag\"\n{}",
                 include_str!("shaders/irradiance.frag")
             );
             let effect = ImageCubeEffect::new(context, &fragment_shader_source)?;
@@ -84,10 +83,8 @@ impl Environment {
         )?;
         {
             let fragment_shader_source = format!(
-                "{}{}{}{}",
+                "{}#include \"shared.frag\"\n#include \"light_shared.frag\"\n{}",
                 super::lighting_model_shader(lighting_model),
-                include_str!("../../core/shared.frag"),
-                include_str!("shaders/light_shared.frag"),
                 include_str!("shaders/prefilter.frag")
             );
             let effect = ImageCubeEffect::new(context, &fragment_shader_source)?;
@@ -124,10 +121,8 @@ impl Environment {
         let effect = ImageEffect::new(
             context,
             &format!(
-                "{}{}{}{}",
+                "{}#include \"shared.frag\"\n#include \"light_shared.frag\"\n{}",
                 super::lighting_model_shader(lighting_model),
-                include_str!("../../core/shared.frag"),
-                include_str!("shaders/light_shared.frag"),
                 include_str!("shaders/brdf.frag")
             ),
         )?;
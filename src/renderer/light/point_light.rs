@@ -70,3 +70,27 @@ impl Light for PointLight {
         Ok(())
     }
 }
+
+///
+/// Writes the point light as the following struct in a uniform block declared with `layout (std140)`, where the color is multiplied by the intensity:
+///
+/// ```notrust
+/// struct PointLight
+/// {
+///     vec3 color;
+///     vec3 attenuation;
+///     vec3 position;
+/// };
+/// ```
+///
+impl Std140 for PointLight {
+    fn alignment() -> usize {
+        16
+    }
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        writer.write(&(self.color.to_vec3() * self.intensity));
+        writer.write(&self.attenuation);
+        writer.write(&self.position);
+        writer.align(16);
+    }
+}
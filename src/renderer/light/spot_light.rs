@@ -103,25 +103,30 @@ impl SpotLight {
             Wrapping::ClampToEdge,
             DepthFormat::Depth32F,
         )?;
+        shadow_texture.set_label("SpotLight shadow map");
         let depth_material = DepthMaterial {
             render_states: RenderStates {
                 write_mask: WriteMask::DEPTH,
+                depth_bias: SHADOW_MAP_DEPTH_BIAS,
                 ..Default::default()
             },
             ..Default::default()
         };
-        shadow_texture
-            .as_depth_target()
-            .clear(ClearState::default())?
-            .write(|| {
-                for geometry in geometries
-                    .iter()
-                    .filter(|g| shadow_camera.in_frustum(&g.aabb()))
-                {
-                    geometry.render_with_material(&depth_material, &shadow_camera, &[])?;
-                }
-                Ok(())
-            })?;
+        self.context.debug_group("SpotLight shadow map", || {
+            shadow_texture
+                .as_depth_target()
+                .clear(ClearState::default())?
+                .write(|| {
+                    for geometry in geometries
+                        .iter()
+                        .filter(|g| shadow_camera.in_frustum(&g.aabb()))
+                    {
+                        geometry.render_with_material(&depth_material, &shadow_camera, &[])?;
+                    }
+                    Ok(())
+                })?;
+            Ok(())
+        })?;
         self.shadow_texture = Some(shadow_texture);
         Ok(())
     }
@@ -219,3 +224,31 @@ impl Light for SpotLight {
         Ok(())
     }
 }
+
+///
+/// Writes the spot light as the following struct in a uniform block declared with `layout (std140)`, where the color is multiplied by the intensity:
+///
+/// ```notrust
+/// struct SpotLight
+/// {
+///     vec3 color;
+///     vec3 attenuation;
+///     vec3 position;
+///     vec3 direction;
+///     float cutoff;
+/// };
+/// ```
+///
+impl Std140 for SpotLight {
+    fn alignment() -> usize {
+        16
+    }
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        writer.write(&(self.color.to_vec3() * self.intensity));
+        writer.write(&self.attenuation);
+        writer.write(&self.position);
+        writer.write(&self.direction.normalize());
+        writer.write(&self.cutoff.0);
+        writer.align(16);
+    }
+}
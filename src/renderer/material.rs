@@ -203,6 +203,32 @@ impl<T: Material> Material for std::rc::Rc<std::cell::RefCell<T>> {
     }
 }
 
+///
+/// Creates a texture containing color information, ie. an albedo or emissive texture.
+/// A [CpuTexture] does not specify its color space, so RGB and RGBA byte data is assumed to be in sRGB color space, which is the case for most image formats,
+/// and is stored in an sRGB texture which makes the GPU convert it to linear color space when sampled, see [Context::set_srgb_color_textures].
+///
+fn color_texture(context: &Context, cpu_texture: &CpuTexture) -> ThreeDResult<Texture2D> {
+    match cpu_texture.data {
+        TextureData::RgbU8(_) | TextureData::RgbaU8(_) if context.srgb_color_textures() => {
+            Texture2D::new_srgb(context, cpu_texture)
+        }
+        _ => Texture2D::new(context, cpu_texture),
+    }
+}
+
+///
+/// Labels each texture with the name of the material followed by the usage of the texture, for example "brick albedo",
+/// so that the textures can be identified in [Context::resource_stats] and in GPU debuggers.
+///
+fn label_textures(material_name: &str, textures: &[(&Option<std::rc::Rc<Texture2D>>, &str)]) {
+    for (texture, usage) in textures {
+        if let Some(texture) = texture {
+            texture.set_label(&format!("{} {}", material_name, usage));
+        }
+    }
+}
+
 fn is_transparent(cpu_material: &CpuMaterial) -> bool {
     cpu_material.albedo.a != 255
         || cpu_material
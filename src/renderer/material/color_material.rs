@@ -35,10 +35,11 @@ impl ColorMaterial {
     /// Constructs a new opaque color material from a [CpuMaterial].
     pub fn new_opaque(context: &Context, cpu_material: &CpuMaterial) -> ThreeDResult<Self> {
         let texture = if let Some(ref cpu_texture) = cpu_material.albedo_texture {
-            Some(Rc::new(Texture2D::new(&context, cpu_texture)?))
+            Some(Rc::new(super::color_texture(context, cpu_texture)?))
         } else {
             None
         };
+        super::label_textures(&cpu_material.name, &[(&texture, "albedo")]);
         Ok(Self {
             color: cpu_material.albedo,
             texture,
@@ -50,10 +51,11 @@ impl ColorMaterial {
     /// Constructs a new transparent color material from a [CpuMaterial].
     pub fn new_transparent(context: &Context, cpu_material: &CpuMaterial) -> ThreeDResult<Self> {
         let texture = if let Some(ref cpu_texture) = cpu_material.albedo_texture {
-            Some(Rc::new(Texture2D::new(&context, cpu_texture)?))
+            Some(Rc::new(super::color_texture(context, cpu_texture)?))
         } else {
             None
         };
+        super::label_textures(&cpu_material.name, &[(&texture, "albedo")]);
         Ok(Self {
             color: cpu_material.albedo,
             texture,
@@ -86,13 +88,16 @@ impl FromCpuMaterial for ColorMaterial {
 impl Material for ColorMaterial {
     fn fragment_shader_source(&self, use_vertex_colors: bool, _lights: &[&dyn Light]) -> String {
         let mut shader = String::new();
-        if self.texture.is_some() {
+        if let Some(ref texture) = self.texture {
             shader.push_str("#define USE_TEXTURE\nin vec2 uvs;\n");
+            if texture.is_srgb() {
+                shader.push_str("#define SRGB_TEXTURE\n");
+            }
         }
         if use_vertex_colors {
             shader.push_str("#define USE_VERTEX_COLORS\nin vec4 col;\n");
         }
-        shader.push_str(include_str!("../../core/shared.frag"));
+        shader.push_str("#include \"shared.frag\"\n");
         shader.push_str(include_str!("shaders/color_material.frag"));
         shader
     }
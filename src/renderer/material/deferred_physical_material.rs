@@ -49,7 +49,7 @@ impl DeferredPhysicalMaterial {
     ///
     pub fn new(context: &Context, cpu_material: &CpuMaterial) -> ThreeDResult<Self> {
         let albedo_texture = if let Some(ref cpu_texture) = cpu_material.albedo_texture {
-            Some(Rc::new(Texture2D::new(&context, cpu_texture)?))
+            Some(Rc::new(super::color_texture(context, cpu_texture)?))
         } else {
             None
         };
@@ -78,10 +78,20 @@ impl DeferredPhysicalMaterial {
             None
         };
         let emissive_texture = if let Some(ref cpu_texture) = cpu_material.emissive_texture {
-            Some(Rc::new(Texture2D::new(&context, cpu_texture)?))
+            Some(Rc::new(super::color_texture(context, cpu_texture)?))
         } else {
             None
         };
+        super::label_textures(
+            &cpu_material.name,
+            &[
+                (&albedo_texture, "albedo"),
+                (&metallic_roughness_texture, "metallic roughness"),
+                (&occlusion_texture, "occlusion"),
+                (&normal_texture, "normal"),
+                (&emissive_texture, "emissive"),
+            ],
+        );
         Ok(Self {
             name: cpu_material.name.clone(),
             albedo: cpu_material.albedo,
@@ -139,7 +149,7 @@ impl FromCpuMaterial for DeferredPhysicalMaterial {
 
 impl Material for DeferredPhysicalMaterial {
     fn fragment_shader_source(&self, use_vertex_colors: bool, _lights: &[&dyn Light]) -> String {
-        let mut output = include_str!("../../core/shared.frag").to_string();
+        let mut output = "#include \"shared.frag\"\n".to_string();
         if self.albedo_texture.is_some()
             || self.metallic_roughness_texture.is_some()
             || self.normal_texture.is_some()
@@ -148,8 +158,11 @@ impl Material for DeferredPhysicalMaterial {
             || self.alpha_cutout.is_some()
         {
             output.push_str("in vec2 uvs;\n");
-            if self.albedo_texture.is_some() {
+            if let Some(ref texture) = self.albedo_texture {
                 output.push_str("#define USE_ALBEDO_TEXTURE;\n");
+                if texture.is_srgb() {
+                    output.push_str("#define SRGB_ALBEDO_TEXTURE;\n");
+                }
             }
             if self.metallic_roughness_texture.is_some() {
                 output.push_str("#define USE_METALLIC_ROUGHNESS_TEXTURE;\n");
@@ -160,8 +173,11 @@ impl Material for DeferredPhysicalMaterial {
             if self.normal_texture.is_some() {
                 output.push_str("#define USE_NORMAL_TEXTURE;\nin vec3 tang;\nin vec3 bitang;\n");
             }
-            if self.emissive_texture.is_some() {
+            if let Some(ref texture) = self.emissive_texture {
                 output.push_str("#define USE_EMISSIVE_TEXTURE;\n");
+                if texture.is_srgb() {
+                    output.push_str("#define SRGB_EMISSIVE_TEXTURE;\n");
+                }
             }
             if self.alpha_cutout.is_some() {
                 output.push_str(
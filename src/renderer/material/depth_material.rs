@@ -11,7 +11,7 @@ pub struct DepthMaterial {
     pub min_distance: Option<f32>,
     /// The maximum distance from the camera to any object. If None, then the far plane of the camera is used.
     pub max_distance: Option<f32>,
-    /// Render states.
+    /// Render states. When used for generating shadow maps, the [RenderStates::depth_bias] can be used to avoid shadow acne.
     pub render_states: RenderStates,
 }
 
@@ -74,7 +74,7 @@ impl PhysicalMaterial {
         is_transparent: bool,
     ) -> ThreeDResult<Self> {
         let albedo_texture = if let Some(ref cpu_texture) = cpu_material.albedo_texture {
-            Some(Rc::new(Texture2D::new(&context, cpu_texture)?))
+            Some(Rc::new(super::color_texture(context, cpu_texture)?))
         } else {
             None
         };
@@ -103,10 +103,20 @@ impl PhysicalMaterial {
             None
         };
         let emissive_texture = if let Some(ref cpu_texture) = cpu_material.emissive_texture {
-            Some(Rc::new(Texture2D::new(&context, cpu_texture)?))
+            Some(Rc::new(super::color_texture(context, cpu_texture)?))
         } else {
             None
         };
+        super::label_textures(
+            &cpu_material.name,
+            &[
+                (&albedo_texture, "albedo"),
+                (&metallic_roughness_texture, "metallic roughness"),
+                (&occlusion_texture, "occlusion"),
+                (&normal_texture, "normal"),
+                (&emissive_texture, "emissive"),
+            ],
+        );
         Ok(Self {
             name: cpu_material.name.clone(),
             albedo: cpu_material.albedo,
@@ -151,8 +161,11 @@ impl Material for PhysicalMaterial {
             || self.emissive_texture.is_some()
         {
             output.push_str("in vec2 uvs;\n");
-            if self.albedo_texture.is_some() {
+            if let Some(ref texture) = self.albedo_texture {
                 output.push_str("#define USE_ALBEDO_TEXTURE;\n");
+                if texture.is_srgb() {
+                    output.push_str("#define SRGB_ALBEDO_TEXTURE;\n");
+                }
             }
             if self.metallic_roughness_texture.is_some() {
                 output.push_str("#define USE_METALLIC_ROUGHNESS_TEXTURE;\n");
@@ -163,8 +176,11 @@ impl Material for PhysicalMaterial {
             if self.normal_texture.is_some() {
                 output.push_str("#define USE_NORMAL_TEXTURE;\nin vec3 tang;\nin vec3 bitang;\n");
             }
-            if self.emissive_texture.is_some() {
+            if let Some(ref texture) = self.emissive_texture {
                 output.push_str("#define USE_EMISSIVE_TEXTURE;\n");
+                if texture.is_srgb() {
+                    output.push_str("#define SRGB_EMISSIVE_TEXTURE;\n");
+                }
             }
         }
         if use_vertex_colors {
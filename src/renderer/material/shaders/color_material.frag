@@ -16,8 +16,12 @@ void main()
     
     #ifdef USE_TEXTURE
     vec4 tex_color = texture(tex, uvs);
+    #ifdef SRGB_TEXTURE
+    outColor *= tex_color;
+    #else
     outColor *= vec4(rgb_from_srgb(tex_color.rgb), tex_color.a);
     #endif
+    #endif
 
     outColor.rgb = srgb_from_rgb(outColor.rgb);
 }
\ No newline at end of file
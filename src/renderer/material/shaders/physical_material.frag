@@ -40,7 +40,11 @@ void main()
     #ifdef ALPHACUT
         if (c.a < acut) discard;
     #endif
-    surface_color *= vec4(rgb_from_srgb(c.rgb), c.a);
+    #ifdef SRGB_ALBEDO_TEXTURE
+        surface_color *= c;
+    #else
+        surface_color *= vec4(rgb_from_srgb(c.rgb), c.a);
+    #endif
 #endif
 #ifdef USE_VERTEX_COLORS
     surface_color *= col;
@@ -70,7 +74,11 @@ void main()
     vec3 total_emissive = emissive.rgb;
 #ifdef USE_EMISSIVE_TEXTURE
     vec4 e = texture(emissiveTexture, uvs);
-    total_emissive *= rgb_from_srgb(e.rgb);
+    #ifdef SRGB_EMISSIVE_TEXTURE
+        total_emissive *= e.rgb;
+    #else
+        total_emissive *= rgb_from_srgb(e.rgb);
+    #endif
 #endif
 
     outColor.rgb = total_emissive + calculate_lighting(cameraPosition, surface_color.rgb, pos, normal, metallic_factor, roughness_factor, occlusion);
@@ -8,8 +8,7 @@ pub struct SkyboxMaterial {
 impl Material for SkyboxMaterial {
     fn fragment_shader_source(&self, _use_vertex_colors: bool, _lights: &[&dyn Light]) -> String {
         format!(
-            "{}{}",
-            include_str!("../../core/shared.frag"),
+            "#include \"shared.frag\"\n{}",
             include_str!("shaders/skybox_material.frag")
         )
     }
@@ -201,8 +201,7 @@ impl ImpostersMaterial {
 impl Material for ImpostersMaterial {
     fn fragment_shader_source(&self, _use_vertex_colors: bool, _lights: &[&dyn Light]) -> String {
         format!(
-            "{}{}",
-            include_str!("../../core/shared.frag"),
+            "#include \"shared.frag\"\n{}",
             include_str!("shaders/imposter.frag")
         )
     }
@@ -0,0 +1,104 @@
+use crate::core::*;
+use crate::renderer::*;
+
+///
+/// Renders objects in the same way as [render_pass], except that objects which were hidden behind other objects in the previous frame are skipped (occlusion culling).
+/// This can reduce the rendering time significantly for scenes where many objects are hidden, for example the buildings in a city,
+/// which cannot be rejected by [Camera::in_frustum].
+///
+/// For each object, an [OcclusionQuery] determines whether the object is visible. If an object was hidden in the previous frame,
+/// only the bounding box of the object is rendered, depth tested but without writing to the color and depth, to test whether the object has become visible.
+/// Since the result of a query is only used when it is available, an object that becomes visible might be missing for a frame or two.
+/// An object is always rendered when the camera is inside its bounding box.
+///
+pub struct OcclusionCulling {
+    context: Context,
+    bounding_box: Mesh,
+    material: ColorMaterial,
+    queries: Vec<OcclusionQuery>,
+    is_visible: Vec<bool>,
+}
+
+impl OcclusionCulling {
+    ///
+    /// Creates a new occlusion culling helper.
+    ///
+    pub fn new(context: &Context) -> ThreeDResult<Self> {
+        Ok(Self {
+            context: context.clone(),
+            bounding_box: Mesh::new(context, &CpuMesh::cube())?,
+            material: ColorMaterial {
+                render_states: RenderStates {
+                    write_mask: WriteMask::NONE,
+                    depth_test: DepthTest::Less,
+                    cull: Cull::None,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            queries: Vec::new(),
+            is_visible: Vec::new(),
+        })
+    }
+
+    ///
+    /// Render the objects, that are inside the frustum of the camera and were visible in the previous frame, with the given lights.
+    /// An object is identified by its index in the list of objects, so the list should contain the same objects in the same order every frame.
+    /// Must be called in the callback given as input to a [RenderTarget], [ColorTarget] or [DepthTarget] write method.
+    ///
+    pub fn render_pass(
+        &mut self,
+        camera: &Camera,
+        objects: &[&dyn Object],
+        lights: &[&dyn Light],
+    ) -> ThreeDResult<()> {
+        self.queries.truncate(objects.len());
+        while self.queries.len() < objects.len() {
+            self.queries.push(OcclusionQuery::new(&self.context, true)?);
+        }
+        self.is_visible.resize(objects.len(), true);
+        for (query, is_visible) in self.queries.iter_mut().zip(self.is_visible.iter_mut()) {
+            if let Some(result) = query.result() {
+                *is_visible = result;
+            }
+        }
+
+        let mut culled_objects = (0..objects.len())
+            .filter(|i| camera.in_frustum(&objects[*i].aabb()))
+            .collect::<Vec<_>>();
+        culled_objects.sort_by(|a, b| cmp_render_order(camera, objects[*a], objects[*b]));
+        for i in culled_objects {
+            let object = objects[i];
+            let aabb = object.aabb();
+            if self.queries[i].is_pending() {
+                // The result of the previous query is not yet available
+                if self.is_visible[i] {
+                    object.render(camera, lights)?;
+                }
+            } else if self.is_visible[i]
+                // The bounding box is clipped when the camera is inside it, so it cannot be used to test the visibility
+                || aabb.distance(camera.position()) <= camera.z_near()
+                || object.is_transparent()
+                || aabb.is_empty()
+                || aabb.is_infinite()
+            {
+                self.queries[i].measure(|| object.render(camera, lights))?;
+            } else {
+                let size = aabb.max() - aabb.min();
+                self.bounding_box.set_transformation(
+                    Mat4::from_translation(aabb.center())
+                        * Mat4::from_nonuniform_scale(0.5 * size.x, 0.5 * size.y, 0.5 * size.z),
+                );
+                let bounding_box = &self.bounding_box;
+                let material = &self.material;
+                let context = &self.context;
+                self.queries[i].measure(|| {
+                    context.depth_test_without_depth_write(|| {
+                        bounding_box.render_with_material(material, camera, &[])
+                    })
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -54,10 +54,14 @@ impl Window {
                     windowed_context.get_proc_address(s) as *const _
                 })
             };
+            let gl = crate::core::Context::from_gl_context(std::sync::Arc::new(context))?;
+            unsafe {
+                gl.load_additional_functions(|s| windowed_context.get_proc_address(s));
+            }
             Ok(Window {
                 windowed_context: Some(windowed_context),
                 event_loop: Some(event_loop),
-                gl: crate::core::Context::from_gl_context(std::sync::Arc::new(context))?,
+                gl,
             })
         }
     }
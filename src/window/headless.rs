@@ -19,6 +19,9 @@ impl Context {
                 headless_context.get_proc_address(s) as *const _
             })
         }))?;
+        unsafe {
+            c.load_additional_functions(|s| headless_context.get_proc_address(s));
+        }
         c.glutin_context = Some(std::rc::Rc::new(headless_context));
         Ok(c)
     }